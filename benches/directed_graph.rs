@@ -100,6 +100,24 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         })
     });
 
+    c.bench_function("dg_find_path_deep", |b| {
+        b.iter(|| {
+            graph_dg.find_path(
+                black_box("1781f676dedf5767f3243db0a9738b35"),
+                black_box("6a7ea7fcf812e2f29af9aac5e74d8636"),
+            )
+        })
+    });
+
+    c.bench_function("dg_find_path_unreachable", |b| {
+        b.iter(|| {
+            graph_dg.find_path(
+                black_box("1f42757d070b2ca2ea732b2b54328216"),
+                black_box("6a7ea7fcf812e2f29af9aac5e74d8636"),
+            )
+        })
+    });
+
     c.bench_function("dg_find_all_paths", |b| {
         b.iter(|| {
             graph_dg.find_all_paths(
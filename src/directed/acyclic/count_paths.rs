@@ -0,0 +1,119 @@
+use super::{topological_sort::topological_sort_from, DirectedAcyclicGraph};
+use crate::{
+    prelude::*,
+    utils::{node_map::LazySet, node_set::NodeVec, sym::Sym},
+};
+
+impl DirectedAcyclicGraph {
+    /// Propagates the number of paths reaching each node down
+    /// to its children following `order`.
+    fn propagate_path_counts_u32(&self, order: &[Sym], counts: &mut [u128]) {
+        for &node in order {
+            let count = counts[node.into_usize()];
+            if count == 0 {
+                continue;
+            }
+            if let LazySet::Initialized(children) = self.children_map.get(node) {
+                for &child in children.iter() {
+                    let child_count = &mut counts[child.into_usize()];
+                    *child_count = child_count.saturating_add(count);
+                }
+            }
+        }
+    }
+
+    /// Returns the number of distinct paths between two nodes
+    /// without enumerating them.
+    ///
+    /// NOTE: The count saturates at `u128::MAX`.
+    pub fn count_paths(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<u128> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+
+        let mut counts = vec![0u128; self.interner.len()];
+        counts[from.into_usize()] = 1;
+        let order = topological_sort_from(&self.dg, &[from]);
+        self.propagate_path_counts_u32(&order, &mut counts);
+
+        Ok(counts[to.into_usize()])
+    }
+
+    /// Returns every leaf in the graph along with the number of
+    /// distinct paths that start on any root and end on that leaf.
+    /// `counts[i]` belongs to `leaves[i]`.
+    ///
+    /// NOTE: The counts saturate at `u128::MAX`.
+    pub fn count_paths_from_roots(&self) -> (NodeVec, Vec<u128>) {
+        let mut counts = vec![0u128; self.interner.len()];
+        for &root in &self.roots {
+            counts[root.into_usize()] = 1;
+        }
+        let order = self.topological_order_u32();
+        self.propagate_path_counts_u32(&order, &mut counts);
+
+        let leaf_counts = self
+            .leaves
+            .iter()
+            .map(|leaf| counts[leaf.into_usize()])
+            .collect();
+        (self.resolve_mul_slice(&self.leaves), leaf_counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    /// Chain of `n` diamonds, there are `2^n` paths from
+    /// `0` to `n`.
+    fn diamonds(n: usize) -> DirectedAcyclicGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..n {
+            builder
+                .add_path([format!("{i}"), format!("{i}a"), format!("{}", i + 1)])
+                .unwrap();
+            builder
+                .add_path([format!("{i}"), format!("{i}b"), format!("{}", i + 1)])
+                .unwrap();
+        }
+        builder.build_acyclic().unwrap()
+    }
+
+    #[test]
+    fn dag_count_paths() {
+        let dag = diamonds(10);
+        assert_eq!(dag.count_paths("0", "10").unwrap(), 1024);
+        assert_eq!(dag.count_paths("3", "5").unwrap(), 4);
+        assert_eq!(dag.count_paths("3", "3").unwrap(), 1);
+        assert_eq!(dag.count_paths("5", "3").unwrap(), 0);
+        assert!(dag.count_paths("0", "Z").is_err());
+        assert_eq!(
+            dag.count_paths("0", "10").unwrap() as usize,
+            dag.find_all_paths("0", "10").unwrap().len()
+        );
+    }
+
+    #[test]
+    fn dag_count_paths_saturates() {
+        let dag = diamonds(200);
+        assert_eq!(dag.count_paths("0", "200").unwrap(), u128::MAX);
+    }
+
+    #[test]
+    fn dag_count_paths_from_roots() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "D"]).unwrap();
+        builder.add_path(["A", "C", "D"]).unwrap();
+        builder.add_path(["X", "D"]).unwrap();
+        builder.add_path(["X", "E"]).unwrap();
+        let dag = builder.build_acyclic().unwrap();
+
+        let (leaves, counts) = dag.count_paths_from_roots();
+        assert_eq!(leaves, ["D", "E"]);
+        assert_eq!(counts, [3, 1]);
+    }
+}
@@ -0,0 +1,320 @@
+use fxhash::FxHashMap;
+
+use super::{topological_sort::topological_sort_from, DirectedAcyclicGraph};
+use crate::{
+    directed::weighted::construct_path_from_prev,
+    prelude::*,
+    utils::{node_map::LazySet, node_set::NodeVec, sym::Sym},
+};
+
+/// Earliest and latest times at which a node can be processed
+/// without delaying the whole graph. See
+/// [`DirectedAcyclicGraph::critical_path`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeSchedule {
+    pub earliest_start: f64,
+    pub earliest_finish: f64,
+    pub latest_start: f64,
+    pub latest_finish: f64,
+    /// How much the node can be delayed without delaying the
+    /// whole graph. Nodes on the critical path have no slack.
+    pub slack: f64,
+}
+
+/// Result of a critical path analysis.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct CriticalPath {
+    /// Longest chain of nodes by duration.
+    pub path: NodeVec,
+    /// Total duration of the graph.
+    pub duration: f64,
+    /// Every node in the graph. `schedule[i]` belongs to `nodes[i]`.
+    pub nodes: NodeVec,
+    pub schedule: Vec<NodeSchedule>,
+    /// Node -> its position in `nodes`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    positions: FxHashMap<&'static str, usize>,
+}
+
+impl CriticalPath {
+    /// Returns the schedule of a node or `None` if the node was
+    /// not part of the analysis.
+    pub fn get(&self, node: impl AsRef<str>) -> Option<NodeSchedule> {
+        self.positions.get(node.as_ref()).map(|&i| self.schedule[i])
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Every node in topological order, parents come before
+    /// their children.
+    pub(crate) fn topological_order_u32(&self) -> Vec<Sym> {
+        topological_sort_from(&self.dg, &self.roots)
+    }
+
+    fn longest_path_u32(
+        &self,
+        node_weight: impl Fn(Sym) -> f64,
+        edge_weight: impl Fn(Sym, Sym) -> f64,
+    ) -> GraphInteractionResult<(Vec<Sym>, f64)> {
+        let mut best = vec![f64::NEG_INFINITY; self.interner.len()];
+        let mut prev = vec![Sym::RESERVED; self.interner.len()];
+        let mut end = None::<(Sym, f64)>;
+
+        for node in self.topological_order_u32() {
+            let mut cost = 0.0;
+            if let LazySet::Initialized(parents) = self.parent_map.get(node) {
+                for &parent in parents.iter() {
+                    let weight = edge_weight(parent, node);
+                    if weight.is_nan() {
+                        return Err(GraphInteractionError::NaNWeight(
+                            self.resolve(parent).into(),
+                            self.resolve(node).into(),
+                        ));
+                    }
+                    let through_parent = best[parent.into_usize()] + weight;
+                    if prev[node.into_usize()].is_reserved() || through_parent > cost {
+                        cost = through_parent;
+                        prev[node.into_usize()] = parent;
+                    }
+                }
+            }
+            let weight = node_weight(node);
+            if weight.is_nan() {
+                return Err(GraphInteractionError::NaNNodeWeight(
+                    self.resolve(node).into(),
+                ));
+            }
+            cost += weight;
+            best[node.into_usize()] = cost;
+            match end {
+                Some((_, end_cost)) if end_cost >= cost => (),
+                _ => end = Some((node, cost)),
+            }
+        }
+
+        Ok(match end {
+            Some((end, cost)) => (construct_path_from_prev(&prev, end), cost),
+            None => (Vec::new(), 0.0),
+        })
+    }
+
+    /// Returns the path with the most edges in the graph.
+    pub fn longest_path(&self) -> NodeVec {
+        let (path, _) = self
+            .longest_path_u32(|_| 0.0, |_, _| 1.0)
+            .expect("Constant weights are never NaN");
+        self.resolve_mul_slice(&path)
+    }
+
+    /// Returns the path with the highest total edge weight in the
+    /// graph and its weight.
+    ///
+    /// Returns an error if an edge has a NaN weight.
+    pub fn longest_path_weighted(&self) -> GraphInteractionResult<(NodeVec, f64)> {
+        let (path, cost) =
+            self.longest_path_u32(|_| 0.0, |parent, child| self.weight_u32(parent, child))?;
+        Ok((self.resolve_mul_slice(&path), cost))
+    }
+
+    /// Returns the path with the highest sum of node weights in
+    /// the graph and its weight. `weight` is called once per node.
+    ///
+    /// Returns an error if `weight` returns NaN for a node.
+    pub fn longest_path_by_node_weight(
+        &self,
+        weight: impl Fn(&str) -> f64,
+    ) -> GraphInteractionResult<(NodeVec, f64)> {
+        let weights = self.node_weights_u32(weight);
+        let (path, cost) = self.longest_path_u32(|node| weights[node.into_usize()], |_, _| 0.0)?;
+        Ok((self.resolve_mul_slice(&path), cost))
+    }
+
+    fn node_weights_u32(&self, weight: impl Fn(&str) -> f64) -> Vec<f64> {
+        let mut weights = vec![0.0; self.interner.len()];
+        for &node in &self.nodes {
+            weights[node.into_usize()] = weight(self.resolve(node));
+        }
+        weights
+    }
+
+    /// Runs a critical path analysis treating each node as a job
+    /// that takes `duration` and each edge as a dependency: a
+    /// child can only start once all of its parents finished.
+    ///
+    /// Returns the critical path, the total duration and the
+    /// earliest/latest start and slack of every node, or an error
+    /// if `duration` returns NaN for a node.
+    pub fn critical_path(
+        &self,
+        duration: impl Fn(&str) -> f64,
+    ) -> GraphInteractionResult<CriticalPath> {
+        let durations = self.node_weights_u32(duration);
+        let (path, total) =
+            self.longest_path_u32(|node| durations[node.into_usize()], |_, _| 0.0)?;
+        let order = self.topological_order_u32();
+
+        let mut earliest_finish = vec![0.0; self.interner.len()];
+        for &node in &order {
+            let mut start = 0.0f64;
+            if let LazySet::Initialized(parents) = self.parent_map.get(node) {
+                for &parent in parents.iter() {
+                    start = start.max(earliest_finish[parent.into_usize()]);
+                }
+            }
+            earliest_finish[node.into_usize()] = start + durations[node.into_usize()];
+        }
+
+        let mut latest_start = vec![0.0; self.interner.len()];
+        for &node in order.iter().rev() {
+            let mut finish = total;
+            if let LazySet::Initialized(children) = self.children_map.get(node) {
+                for &child in children.iter() {
+                    finish = finish.min(latest_start[child.into_usize()]);
+                }
+            }
+            latest_start[node.into_usize()] = finish - durations[node.into_usize()];
+        }
+
+        let schedule = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let node = node.into_usize();
+                let earliest_start = earliest_finish[node] - durations[node];
+                NodeSchedule {
+                    earliest_start,
+                    earliest_finish: earliest_finish[node],
+                    latest_start: latest_start[node],
+                    latest_finish: latest_start[node] + durations[node],
+                    slack: latest_start[node] - earliest_start,
+                }
+            })
+            .collect();
+
+        let nodes = self.resolve_mul_slice(&self.nodes);
+        let positions = nodes
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| (node, i))
+            .collect();
+        Ok(CriticalPath {
+            path: self.resolve_mul_slice(&path),
+            duration: total,
+            nodes,
+            schedule,
+            positions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn dag_longest_path() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "D"]).unwrap();
+        builder.add_path(["A", "D"]).unwrap();
+        builder.add_path(["B", "E", "F", "C"]).unwrap();
+        builder.add_path(["X", "D"]).unwrap();
+        let dag = builder.build_acyclic().unwrap();
+
+        assert_eq!(dag.longest_path(), ["A", "B", "E", "F", "C", "D"]);
+    }
+
+    #[test]
+    fn dag_longest_path_weighted() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 1.0);
+        builder.add_weighted_edge("B", "C", 1.0);
+        builder.add_weighted_edge("A", "C", 5.0);
+        builder.add_weighted_edge("C", "D", 0.5);
+        let dag = builder.build_acyclic().unwrap();
+
+        let (path, cost) = dag.longest_path_weighted().unwrap();
+        assert_eq!(path, ["A", "C", "D"]);
+        assert_eq!(cost, 5.5);
+    }
+
+    #[test]
+    fn dag_longest_path_nan_weight() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 1.0);
+        builder.add_weighted_edge("B", "C", f64::NAN);
+        builder.add_weighted_edge("A", "C", 0.5);
+        let dag = builder.build_acyclic().unwrap();
+        assert!(matches!(
+            dag.longest_path_weighted(),
+            Err(GraphInteractionError::NaNWeight(..))
+        ));
+
+        assert!(matches!(
+            dag.longest_path_by_node_weight(|node| if node == "B" { f64::NAN } else { 1.0 }),
+            Err(GraphInteractionError::NaNNodeWeight(..))
+        ));
+        assert!(matches!(
+            dag.critical_path(|node| if node == "B" { f64::NAN } else { 1.0 }),
+            Err(GraphInteractionError::NaNNodeWeight(..))
+        ));
+    }
+
+    #[test]
+    fn dag_longest_path_by_node_weight() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "D"]).unwrap();
+        builder.add_path(["A", "C", "D"]).unwrap();
+        let dag = builder.build_acyclic().unwrap();
+
+        let (path, cost) = dag
+            .longest_path_by_node_weight(|node| match node {
+                "C" => 10.0,
+                _ => 1.0,
+            })
+            .unwrap();
+        assert_eq!(path, ["A", "C", "D"]);
+        assert_eq!(cost, 12.0);
+    }
+
+    #[test]
+    fn dag_critical_path() {
+        // start -> compile (3) -> test (2) -> deploy (1)
+        //       -> docs (1) ------------------^
+        let mut builder = DirectedGraphBuilder::new();
+        builder
+            .add_path(["start", "compile", "test", "deploy"])
+            .unwrap();
+        builder.add_path(["start", "docs", "deploy"]).unwrap();
+        let dag = builder.build_acyclic().unwrap();
+
+        let analysis = dag
+            .critical_path(|node| match node {
+                "compile" => 3.0,
+                "test" => 2.0,
+                "docs" => 1.0,
+                "deploy" => 1.0,
+                _ => 0.0,
+            })
+            .unwrap();
+
+        assert_eq!(analysis.path, ["start", "compile", "test", "deploy"]);
+        assert_eq!(analysis.duration, 6.0);
+        assert_eq!(analysis.nodes.len(), analysis.schedule.len());
+
+        let docs = analysis.get("docs").unwrap();
+        assert_eq!(docs.earliest_start, 0.0);
+        assert_eq!(docs.earliest_finish, 1.0);
+        assert_eq!(docs.latest_start, 4.0);
+        assert_eq!(docs.latest_finish, 5.0);
+        assert_eq!(docs.slack, 4.0);
+
+        let test = analysis.get("test").unwrap();
+        assert_eq!(test.earliest_start, 3.0);
+        assert_eq!(test.slack, 0.0);
+
+        assert!(analysis.get("missing").is_none());
+    }
+}
@@ -0,0 +1,235 @@
+use super::{topological_sort::topological_sort, DirectedAcyclicGraph};
+use crate::{
+    prelude::*,
+    utils::{node_map::LazySet, node_set::NodeVec, sym::Sym},
+};
+use fxhash::FxHashSet;
+
+impl DirectedGraph {
+    /// Collects every ancestor of `node` (including `node`
+    /// itself) into `out`.
+    pub(crate) fn ancestors_u32(&self, node: Sym, out: &mut FxHashSet<Sym>) {
+        let mut to_visit = vec![node];
+        while let Some(node) = to_visit.pop() {
+            if !out.insert(node) {
+                continue;
+            }
+            if let LazySet::Initialized(parents) = self.parent_map.get(node) {
+                to_visit.extend(parents.iter().copied());
+            }
+        }
+    }
+
+    /// Keeps only the nodes in `common` that have no child inside
+    /// `common`. Since a set of common ancestors is closed upwards
+    /// these are exactly the lowest ones.
+    fn lowest_in_u32(
+        &self,
+        common: impl Iterator<Item = Sym>,
+        is_common: impl Fn(Sym) -> bool,
+    ) -> Vec<Sym> {
+        let mut lowest: Vec<Sym> = common
+            .filter(|&node| match self.children_map.get(node) {
+                LazySet::Initialized(children) => !children.iter().any(|&child| is_common(child)),
+                _ => true,
+            })
+            .collect();
+        lowest.sort_unstable();
+        lowest
+    }
+}
+
+impl DirectedAcyclicGraph {
+    /// Returns the lowest common ancestors of `a` and `b`.
+    ///
+    /// A node is considered an ancestor of itself, so if `a` is an
+    /// ancestor of `b` the result is `[a]`. Since a DAG may have
+    /// more than one minimal common ancestor all of them are
+    /// returned. The result is empty if the nodes share no
+    /// ancestor.
+    pub fn lowest_common_ancestors(
+        &self,
+        a: impl AsRef<str>,
+        b: impl AsRef<str>,
+    ) -> GraphInteractionResult<NodeVec> {
+        self.lowest_common_ancestors_multi([a.as_ref(), b.as_ref()])
+    }
+
+    /// Returns the lowest common ancestors of every node in
+    /// `nodes`. See [`DirectedAcyclicGraph::lowest_common_ancestors`].
+    ///
+    /// If you need to run many queries on the same graph, build a
+    /// [`LcaIndex`] with [`DirectedAcyclicGraph::lca_index`] instead.
+    pub fn lowest_common_ancestors_multi(
+        &self,
+        nodes: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<NodeVec> {
+        let nodes_buf = unsafe { self.u32x1_vec_0() };
+        self.get_internal_mul(nodes, nodes_buf)?;
+
+        let mut nodes = nodes_buf.iter().copied();
+        let mut common = FxHashSet::default();
+        if let Some(first) = nodes.next() {
+            self.ancestors_u32(first, &mut common);
+        }
+        let mut ancestors = FxHashSet::default();
+        for node in nodes {
+            ancestors.clear();
+            self.ancestors_u32(node, &mut ancestors);
+            common.retain(|node| ancestors.contains(node));
+        }
+
+        let lowest = self.lowest_in_u32(common.iter().copied(), |node| common.contains(&node));
+        Ok(self.resolve_mul_slice(&lowest))
+    }
+
+    /// Precomputes the ancestors of every node so that lowest
+    /// common ancestor queries do not need to traverse the graph.
+    ///
+    /// NOTE: The index stores the full set of ancestors of each
+    /// node, its memory usage grows with the depth of the graph.
+    pub fn lca_index(&self) -> LcaIndex<'_> {
+        let order = topological_sort(&self.dg).expect("A DAG can always be sorted");
+        let mut ancestors = vec![Box::<[Sym]>::default(); self.interner.len()];
+        let mut buf = Vec::new();
+
+        // The topological order goes from the leaves up, we need
+        // every parent to be computed before its children.
+        for &node in order.iter().rev() {
+            buf.push(node);
+            if let LazySet::Initialized(parents) = self.parent_map.get(node) {
+                for parent in parents.iter() {
+                    buf.extend_from_slice(&ancestors[parent.into_usize()]);
+                }
+            }
+            buf.sort_unstable();
+            buf.dedup();
+            ancestors[node.into_usize()] = buf.as_slice().into();
+            buf.clear();
+        }
+
+        LcaIndex {
+            dag: self,
+            ancestors,
+        }
+    }
+}
+
+/// Precomputed ancestor sets of a [`DirectedAcyclicGraph`] for
+/// answering repeated lowest common ancestor queries.
+pub struct LcaIndex<'a> {
+    dag: &'a DirectedAcyclicGraph,
+    /// Sorted ancestors of every node (including itself)
+    /// indexed by symbol.
+    ancestors: Vec<Box<[Sym]>>,
+}
+
+impl LcaIndex<'_> {
+    /// Same as [`DirectedAcyclicGraph::lowest_common_ancestors`].
+    pub fn lowest_common_ancestors(
+        &self,
+        a: impl AsRef<str>,
+        b: impl AsRef<str>,
+    ) -> GraphInteractionResult<NodeVec> {
+        self.lowest_common_ancestors_multi([a.as_ref(), b.as_ref()])
+    }
+
+    /// Same as [`DirectedAcyclicGraph::lowest_common_ancestors_multi`].
+    pub fn lowest_common_ancestors_multi(
+        &self,
+        nodes: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<NodeVec> {
+        let mut nodes_buf = Vec::new();
+        self.dag.get_internal_mul(nodes, &mut nodes_buf)?;
+
+        let mut common: Vec<Sym> = match nodes_buf.first() {
+            Some(first) => self.ancestors[first.into_usize()].to_vec(),
+            None => Vec::new(),
+        };
+        for node in nodes_buf.iter().skip(1) {
+            let ancestors = &self.ancestors[node.into_usize()];
+            common.retain(|node| ancestors.binary_search(node).is_ok());
+        }
+
+        let lowest = self.dag.lowest_in_u32(common.iter().copied(), |node| {
+            common.binary_search(&node).is_ok()
+        });
+        Ok(self.dag.resolve_mul_slice(&lowest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::directed::DirectedGraphBuilder;
+
+    fn diamond() -> crate::prelude::DirectedAcyclicGraph {
+        //     A
+        //    / \
+        //   B   C
+        //   |\ /|
+        //   | X |
+        //   |/ \|
+        //   D   E
+        //   |
+        //   F
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_edge("A", "B");
+        builder.add_edge("A", "C");
+        builder.add_edge("B", "D");
+        builder.add_edge("B", "E");
+        builder.add_edge("C", "D");
+        builder.add_edge("C", "E");
+        builder.add_edge("D", "F");
+        builder.add_edge("X", "F");
+        builder.build_acyclic().unwrap()
+    }
+
+    #[test]
+    fn dag_lowest_common_ancestors() {
+        let dag = diamond();
+        assert_eq!(dag.lowest_common_ancestors("D", "E").unwrap(), ["B", "C"]);
+        assert_eq!(dag.lowest_common_ancestors("B", "C").unwrap(), ["A"]);
+        assert_eq!(dag.lowest_common_ancestors("B", "F").unwrap(), ["B"]);
+        assert_eq!(dag.lowest_common_ancestors("F", "F").unwrap(), ["F"]);
+        assert!(dag.lowest_common_ancestors("X", "E").unwrap().is_empty());
+        assert!(dag.lowest_common_ancestors("X", "Z").is_err());
+    }
+
+    #[test]
+    fn dag_lowest_common_ancestors_multi() {
+        let dag = diamond();
+        assert_eq!(
+            dag.lowest_common_ancestors_multi(["F", "E", "D"]).unwrap(),
+            ["B", "C"]
+        );
+        assert_eq!(
+            dag.lowest_common_ancestors_multi(["F", "E", "C"]).unwrap(),
+            ["C"]
+        );
+        assert_eq!(
+            dag.lowest_common_ancestors_multi(["F", "X"]).unwrap(),
+            ["X"]
+        );
+    }
+
+    #[test]
+    fn dag_lca_index_matches_direct_queries() {
+        let dag = diamond();
+        let index = dag.lca_index();
+        let nodes = dag.nodes();
+        for a in nodes.iter() {
+            for b in nodes.iter() {
+                assert_eq!(
+                    index.lowest_common_ancestors(a, b).unwrap(),
+                    dag.lowest_common_ancestors(a, b).unwrap(),
+                );
+            }
+        }
+        assert_eq!(
+            index
+                .lowest_common_ancestors_multi(["F", "E", "D"])
+                .unwrap(),
+            ["B", "C"]
+        );
+    }
+}
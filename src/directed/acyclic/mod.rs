@@ -1,10 +1,12 @@
-use crate::{
-    directed::DirectedGraph,
-    prelude::*,
-    utils::{node_set::NodeVec, sym::Sym},
-};
+use crate::{directed::DirectedGraph, prelude::*, utils::node_set::NodeVec};
 use std::{num::NonZeroUsize, ops::Deref};
+mod count_paths;
+mod longest_path;
+mod lowest_common_ancestors;
 mod topological_sort;
+mod weighted;
+pub use longest_path::{CriticalPath, NodeSchedule};
+pub use lowest_common_ancestors::LcaIndex;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use topological_sort::topological_sort;
@@ -40,66 +42,50 @@ impl DirectedAcyclicGraph {
         *self.dg
     }
 
+    /// Returns a graph with every edge of both graphs. See
+    /// [`DirectedGraph::union`].
+    ///
+    /// Returns an error if merging the graphs creates a cycle.
+    pub fn union(
+        &self,
+        other: &DirectedAcyclicGraph,
+    ) -> Result<DirectedAcyclicGraph, GraphHasCycle> {
+        DirectedAcyclicGraph::build(self.dg.union(&other.dg))
+    }
+
+    /// Returns a graph with the edges present in both graphs. See
+    /// [`DirectedGraph::intersection`].
+    pub fn intersection(&self, other: &DirectedAcyclicGraph) -> DirectedAcyclicGraph {
+        let dg = Box::new(self.dg.intersection(&other.dg));
+        DirectedAcyclicGraph { dg }
+    }
+
+    /// Returns a graph with the edges of `self` that are not in
+    /// `other`. See [`DirectedGraph::difference`].
+    pub fn difference(&self, other: &DirectedAcyclicGraph) -> DirectedAcyclicGraph {
+        let dg = Box::new(self.dg.difference(&other.dg));
+        DirectedAcyclicGraph { dg }
+    }
+
+    /// Returns the graph with the direction of every edge flipped.
+    /// Reversing a DAG keeps it acyclic so it is not checked
+    /// again.
+    pub fn reversed(self) -> DirectedAcyclicGraph {
+        DirectedAcyclicGraph {
+            dg: Box::new(self.dg.reversed()),
+        }
+    }
+
     /// Finds all paths on a DAG using DFS
+    ///
+    /// NOTE: Every path is kept in memory. Use
+    /// [`DirectedGraph::paths_iter`] to enumerate them lazily.
     pub fn find_all_paths(
         &self,
         from: impl AsRef<str>,
         to: impl AsRef<str>,
     ) -> GraphInteractionResult<Vec<NodeVec>> {
-        // Helper function to perform DFS
-        #[inline]
-        fn dfs(
-            graph: &DirectedAcyclicGraph,
-            current: Sym,
-            goal_id: Sym,
-            current_path: &mut Vec<Sym>,
-            all_paths: &mut Vec<Sym>,
-            children_buffer: &mut Vec<Sym>,
-        ) {
-            // Add current node to path
-            current_path.push(current);
-
-            // Check if the current node is the goal
-            if current == goal_id {
-                all_paths.extend_from_slice(current_path);
-                all_paths.push(Sym::RESERVED);
-            } else {
-                let children_start_index_local = children_buffer.len();
-                graph.children_u32(&[current], children_buffer);
-                (children_start_index_local..children_buffer.len()).for_each(|_| {
-                    match children_buffer.pop() {
-                        Some(child) => dfs(
-                            graph,
-                            child,
-                            goal_id,
-                            current_path,
-                            all_paths,
-                            children_buffer,
-                        ),
-                        None => unsafe { std::hint::unreachable_unchecked() },
-                    };
-                });
-            }
-
-            // Backtrack to explore another path
-            current_path.pop();
-        }
-
-        let from = self.get_internal(from)?;
-        let to = self.get_internal(to)?;
-
-        let current_path = unsafe { self.dg.u32x1_vec_0() };
-        let children = unsafe { self.dg.u32x1_vec_1() };
-        let all_paths = unsafe { self.dg.u32x1_vec_2() };
-
-        // Start DFS from the start node
-        dfs(self, from, to, current_path, all_paths, children);
-
-        Ok(all_paths
-            .split(|&n| n.is_reserved())
-            .filter(|p| !p.is_empty())
-            .map(|path| self.resolve_mul_slice(path))
-            .collect())
+        Ok(self.paths_iter(from, to)?.collect())
     }
 
     pub fn subset(&self, node: impl AsRef<str>) -> GraphInteractionResult<DirectedAcyclicGraph> {
@@ -114,6 +100,24 @@ impl DirectedAcyclicGraph {
         let dg = Box::new(self.dg.subset_multi(node)?);
         Ok(DirectedAcyclicGraph { dg })
     }
+    pub fn subset_by_labels(
+        &self,
+        node: impl AsRef<str>,
+        labels: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<DirectedAcyclicGraph> {
+        let dg = Box::new(self.dg.subset_by_labels(node, labels)?);
+        Ok(DirectedAcyclicGraph { dg })
+    }
+
+    pub fn subset_multi_by_labels(
+        &self,
+        nodes: impl IntoIterator<Item = impl AsRef<str>>,
+        labels: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<DirectedAcyclicGraph> {
+        let dg = Box::new(self.dg.subset_multi_by_labels(nodes, labels)?);
+        Ok(DirectedAcyclicGraph { dg })
+    }
+
     pub fn subset_multi_with_limit(
         &self,
         node: impl IntoIterator<Item = impl AsRef<str>>,
@@ -188,10 +192,12 @@ mod tests {
     #[test]
     fn test_find_all_paths_many_paths() {
         let mut builder = DirectedGraphBuilder::new();
-        builder.add_path(["0", "111", "222", "333", "444", "4"]);
-        builder.add_path(["0", "999", "4"]);
-        builder.add_path(["0", "1", "2", "3", "4"]);
-        builder.add_path(["0", "4"]);
+        builder
+            .add_path(["0", "111", "222", "333", "444", "4"])
+            .unwrap();
+        builder.add_path(["0", "999", "4"]).unwrap();
+        builder.add_path(["0", "1", "2", "3", "4"]).unwrap();
+        builder.add_path(["0", "4"]).unwrap();
         let graph = builder.build_acyclic().unwrap();
 
         let paths = graph.find_all_paths("0", "4").unwrap();
@@ -2,6 +2,7 @@ use crate::{
     prelude::*,
     utils::{node_map::LazySet, sym::Sym},
 };
+use fxhash::FxHashSet;
 
 pub fn topological_sort(dg: &DirectedGraph) -> Result<Vec<Sym>, GraphHasCycle> {
     let mut dg = dg.clone();
@@ -20,7 +21,7 @@ pub fn topological_sort(dg: &DirectedGraph) -> Result<Vec<Sym>, GraphHasCycle> {
             }
             if let LazySet::Initialized(node_parents) = dg.parent_map.get_mut(node) {
                 if node_parents.remove(&parent) {
-                    dg.n_edges -= 1;
+                    dg.n_edges -= dg.edge_multiplicity_u32(parent, node);
                 }
             }
 
@@ -44,6 +45,42 @@ pub fn topological_sort(dg: &DirectedGraph) -> Result<Vec<Sym>, GraphHasCycle> {
     Ok(res)
 }
 
+/// Returns the nodes reachable from `starts` ordered so that
+/// every parent comes before its children.
+///
+/// NOTE: This assumes the graph is acyclic.
+pub(crate) fn topological_sort_from(dg: &DirectedGraph, starts: &[Sym]) -> Vec<Sym> {
+    let mut visited = FxHashSet::default();
+    let mut order = Vec::new();
+    // The flag indicates whether the children of the node
+    // have already been pushed to the stack
+    let mut stack = Vec::new();
+
+    for &start in starts {
+        stack.push((start, false));
+        while let Some((node, expanded)) = stack.pop() {
+            if expanded {
+                order.push(node);
+                continue;
+            }
+            if !visited.insert(node) {
+                continue;
+            }
+            stack.push((node, true));
+            if let LazySet::Initialized(children) = dg.children_map.get(node) {
+                for &child in children.iter() {
+                    if !visited.contains(&child) {
+                        stack.push((child, false));
+                    }
+                }
+            }
+        }
+    }
+
+    order.reverse();
+    order
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -69,9 +106,9 @@ mod tests {
     fn test_topologically_sort_paths() {
         let mut builder = DirectedGraphBuilder::new();
 
-        builder.add_path(["Hello", "World"]);
-        builder.add_path(["Hello", "Everybody", "Andrés"]);
-        builder.add_path(["Hello", "Everybody", "Andrea"]);
+        builder.add_path(["Hello", "World"]).unwrap();
+        builder.add_path(["Hello", "Everybody", "Andrés"]).unwrap();
+        builder.add_path(["Hello", "Everybody", "Andrea"]).unwrap();
 
         let graph = builder.build_directed();
 
@@ -90,4 +127,24 @@ mod tests {
 
         assert!(topological_sort(&graph).is_err());
     }
+
+    #[test]
+    fn test_topologically_sort_from() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "D"]).unwrap();
+        builder.add_path(["A", "D"]).unwrap();
+        builder.add_path(["B", "E", "C"]).unwrap();
+        builder.add_path(["X", "C"]).unwrap();
+        let graph = builder.build_directed();
+
+        let order = topological_sort_from(&graph, &[graph.get_internal("A").unwrap()]);
+        let order: Vec<_> = order.into_iter().map(|n| graph.resolve(n)).collect();
+        let position = |node| order.iter().position(|&n| n == node).unwrap();
+
+        assert_eq!(order.len(), 5);
+        assert!(position("A") < position("B"));
+        assert!(position("B") < position("E"));
+        assert!(position("E") < position("C"));
+        assert!(position("C") < position("D"));
+    }
 }
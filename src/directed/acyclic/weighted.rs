@@ -0,0 +1,116 @@
+use super::{topological_sort::topological_sort_from, DirectedAcyclicGraph};
+use crate::{
+    directed::weighted::construct_path_from_prev,
+    prelude::*,
+    utils::{node_map::LazySet, node_set::NodeVec, sym::Sym},
+};
+
+impl DirectedAcyclicGraph {
+    /// Finds the path with the lowest total weight between two
+    /// nodes. Returns the path and its cost or `None` if `to` is
+    /// not reachable from `from`.
+    ///
+    /// Unlike [`DirectedGraph::shortest_path_weighted`] this runs
+    /// in linear time by relaxing the edges in topological order,
+    /// which also makes it work with negative weights.
+    ///
+    /// Returns an error if a NaN weight is found while exploring
+    /// the graph.
+    pub fn shortest_path_weighted(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<Option<(NodeVec, f64)>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+
+        let mut dist = vec![f64::INFINITY; self.interner.len()];
+        let mut prev = vec![Sym::RESERVED; self.interner.len()];
+        dist[from.into_usize()] = 0.0;
+
+        for node in topological_sort_from(&self.dg, &[from]) {
+            if node == to {
+                break;
+            }
+            let cost = dist[node.into_usize()];
+            if let LazySet::Initialized(children) = self.children_map.get(node) {
+                for &child in children.iter() {
+                    let weight = self.weight_u32(node, child);
+                    if weight.is_nan() {
+                        return Err(GraphInteractionError::NaNWeight(
+                            self.resolve(node).into(),
+                            self.resolve(child).into(),
+                        ));
+                    }
+                    let next = cost + weight;
+                    if next < dist[child.into_usize()] {
+                        dist[child.into_usize()] = next;
+                        prev[child.into_usize()] = node;
+                    }
+                }
+            }
+        }
+
+        let cost = dist[to.into_usize()];
+        if cost == f64::INFINITY {
+            return Ok(None);
+        }
+        let path = construct_path_from_prev(&prev, to);
+        Ok(Some((self.resolve_mul_slice(&path), cost)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn dag_shortest_path_weighted() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 1.0);
+        builder.add_weighted_edge("B", "C", 1.0);
+        builder.add_weighted_edge("C", "D", 1.0);
+        builder.add_weighted_edge("A", "D", 5.0);
+        builder.add_weighted_edge("A", "E", 0.5);
+        builder.add_edge("E", "D");
+        let dag = builder.clone().build_acyclic().unwrap();
+
+        let (path, cost) = dag.shortest_path_weighted("A", "D").unwrap().unwrap();
+        assert_eq!(path, ["A", "E", "D"]);
+        assert_eq!(cost, 1.5);
+        assert!(dag.shortest_path_weighted("D", "A").unwrap().is_none());
+
+        // Negative weights are allowed on DAGs
+        builder.add_weighted_edge("B", "C", -3.0);
+        let dag = builder.build_acyclic().unwrap();
+        let (path, cost) = dag.shortest_path_weighted("A", "D").unwrap().unwrap();
+        assert_eq!(path, ["A", "B", "C", "D"]);
+        assert_eq!(cost, -1.0);
+    }
+
+    #[test]
+    fn dag_shortest_path_weighted_nan_weight() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 1.0);
+        builder.add_weighted_edge("B", "C", f64::NAN);
+        let dag = builder.build_acyclic().unwrap();
+        assert!(matches!(
+            dag.shortest_path_weighted("A", "C"),
+            Err(GraphInteractionError::NaNWeight(..))
+        ));
+        assert!(dag.shortest_path_weighted("A", "B").unwrap().is_some());
+    }
+
+    #[test]
+    fn dag_shortest_path_weighted_matches_dijkstra() {
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..50 {
+            builder.add_weighted_edge(i.to_string(), (i + 1).to_string(), (i % 7) as f64);
+            builder.add_weighted_edge(i.to_string(), (i + 3).to_string(), (i % 5) as f64 * 2.0);
+        }
+        let dag = builder.build_acyclic().unwrap();
+        let dag_result = dag.shortest_path_weighted("0", "50").unwrap().unwrap();
+        let dg_result = dag.dg.shortest_path_weighted("0", "50").unwrap().unwrap();
+        assert_eq!(dag_result.1, dg_result.1);
+    }
+}
@@ -0,0 +1,231 @@
+use fxhash::FxHashSet;
+
+use super::dominators::immediate_dominators;
+use crate::{
+    prelude::*,
+    utils::{node_set::NodeVec, sym::Sym},
+};
+
+impl DirectedGraph {
+    /// Runs Tarjan's algorithm on the undirected structure of the
+    /// graph. Every edge is walked in both directions, so two
+    /// nodes connected both ways are never split by removing one
+    /// edge. Returns the articulation points and the bridges.
+    fn articulation_u32(&self) -> (Vec<Sym>, Vec<(Sym, Sym)>) {
+        let n_symbols = self.interner.len();
+        let neighbors = |node: Sym| -> Vec<Sym> {
+            self.children_map
+                .values(node)
+                .chain(self.parent_map.values(node))
+                .collect()
+        };
+
+        let mut discovery = vec![usize::MAX; n_symbols];
+        let mut low = vec![usize::MAX; n_symbols];
+        let mut is_articulation = vec![false; n_symbols];
+        let mut bridges = Vec::new();
+        let mut time = 0;
+
+        for &start in &self.nodes {
+            if discovery[start.into_usize()] != usize::MAX {
+                continue;
+            }
+            discovery[start.into_usize()] = time;
+            low[start.into_usize()] = time;
+            time += 1;
+            let mut n_root_children = 0;
+
+            // Node, the node it was reached from, its neighbors, the
+            // next neighbor to visit and whether the edge back to
+            // the parent was already skipped
+            let mut stack = vec![(start, Sym::RESERVED, neighbors(start), 0, false)];
+            while let Some((node, parent, node_neighbors, next, skipped_parent)) = stack.last_mut()
+            {
+                let node = *node;
+                let parent = *parent;
+                if let Some(&neighbor) = node_neighbors.get(*next) {
+                    *next += 1;
+                    // Only the edge we came through is skipped, any
+                    // other edge to the parent is a back edge
+                    if neighbor == parent && !*skipped_parent {
+                        *skipped_parent = true;
+                        continue;
+                    }
+                    if discovery[neighbor.into_usize()] == usize::MAX {
+                        discovery[neighbor.into_usize()] = time;
+                        low[neighbor.into_usize()] = time;
+                        time += 1;
+                        if node == start {
+                            n_root_children += 1;
+                        }
+                        stack.push((neighbor, node, neighbors(neighbor), 0, false));
+                    } else {
+                        low[node.into_usize()] =
+                            low[node.into_usize()].min(discovery[neighbor.into_usize()]);
+                    }
+                    continue;
+                }
+
+                stack.pop();
+                if parent == Sym::RESERVED {
+                    continue;
+                }
+                low[parent.into_usize()] = low[parent.into_usize()].min(low[node.into_usize()]);
+                if parent != start && low[node.into_usize()] >= discovery[parent.into_usize()] {
+                    is_articulation[parent.into_usize()] = true;
+                }
+                if low[node.into_usize()] > discovery[parent.into_usize()] {
+                    match self.has_edge_u32(parent, node) {
+                        true => bridges.push((parent, node)),
+                        false => bridges.push((node, parent)),
+                    }
+                }
+            }
+
+            if n_root_children > 1 {
+                is_articulation[start.into_usize()] = true;
+            }
+        }
+
+        let articulation_points = self
+            .nodes
+            .iter()
+            .copied()
+            .filter(|node| is_articulation[node.into_usize()])
+            .collect();
+        (articulation_points, bridges)
+    }
+
+    /// Returns the nodes whose removal splits the graph into more
+    /// connected components, ignoring the direction of the edges.
+    pub fn articulation_points(&self) -> NodeVec {
+        let (articulation_points, _) = self.articulation_u32();
+        self.resolve_mul_slice(&articulation_points)
+    }
+
+    /// Returns the edges whose removal splits the graph into more
+    /// connected components, ignoring the direction of the edges.
+    /// Edges are returned as `(parent, child)`.
+    pub fn bridges(&self) -> Vec<(&str, &str)> {
+        let (_, bridges) = self.articulation_u32();
+        bridges
+            .into_iter()
+            .map(|(parent, child)| (self.resolve(parent), self.resolve(child)))
+            .collect()
+    }
+
+    /// Returns the nodes whose removal leaves at least one of
+    /// `leaves` unreachable from `root`, following the direction
+    /// of the edges. `root` and `leaves` are never included.
+    ///
+    /// Leaves that can not be reached from `root` are ignored.
+    pub fn articulation_points_between(
+        &self,
+        root: impl AsRef<str>,
+        leaves: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<NodeVec> {
+        let root = self.get_internal(root)?;
+        let leaves_buf = unsafe { self.u32x1_vec_0() };
+        self.get_internal_mul(leaves, leaves_buf)?;
+
+        let mut idoms = vec![Sym::RESERVED; self.interner.len()];
+        for (idom, node) in immediate_dominators(&self.children_map, &self.parent_map, root) {
+            idoms[node.into_usize()] = idom;
+        }
+
+        // Every node dominating a leaf is on all paths to it
+        let excluded: FxHashSet<Sym> = leaves_buf.iter().copied().chain([root]).collect();
+        let mut cut_nodes = FxHashSet::default();
+        for &leaf in leaves_buf.iter() {
+            let mut node = idoms[leaf.into_usize()];
+            while node != Sym::RESERVED && node != root {
+                if !excluded.contains(&node) && !cut_nodes.insert(node) {
+                    break;
+                }
+                node = idoms[node.into_usize()];
+            }
+        }
+
+        let cut_nodes: Vec<Sym> = self
+            .nodes
+            .iter()
+            .copied()
+            .filter(|node| cut_nodes.contains(node))
+            .collect();
+        Ok(self.resolve_mul_slice(&cut_nodes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn sorted(nodes: NodeVec) -> Vec<String> {
+        let mut nodes: Vec<String> = nodes.iter().map(|node| node.to_string()).collect();
+        nodes.sort();
+        nodes
+    }
+
+    fn graph() -> DirectedGraph {
+        // A triangle connected to a chain
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "A"]).unwrap();
+        builder.add_path(["C", "D", "E"]).unwrap();
+        builder.add_path(["F", "E"]).unwrap();
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_articulation_points() {
+        let dg = graph();
+        assert_eq!(sorted(dg.articulation_points()), ["C", "D", "E"]);
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "A"]).unwrap();
+        assert!(builder.build_directed().articulation_points().is_empty());
+    }
+
+    #[test]
+    fn dg_bridges() {
+        let dg = graph();
+        let mut bridges = dg.bridges();
+        bridges.sort();
+        assert_eq!(bridges, [("C", "D"), ("D", "E"), ("F", "E")]);
+
+        // Edges in both directions are two separate connections
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "A"]).unwrap();
+        builder.add_path(["B", "C"]).unwrap();
+        let dg = builder.build_directed();
+        assert_eq!(dg.bridges(), [("B", "C")]);
+        assert_eq!(dg.articulation_points(), ["B"]);
+    }
+
+    #[test]
+    fn dg_articulation_points_between() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["root", "A", "B", "leaf1"]).unwrap();
+        builder.add_path(["A", "C", "B"]).unwrap();
+        builder.add_path(["root", "D", "leaf2"]).unwrap();
+        builder.add_path(["root", "leaf2"]).unwrap();
+        let dg = builder.build_directed();
+
+        assert_eq!(
+            sorted(dg.articulation_points_between("root", ["leaf1"]).unwrap()),
+            ["A", "B"]
+        );
+        assert!(dg
+            .articulation_points_between("root", ["leaf2"])
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            sorted(dg.articulation_points_between("A", ["leaf1", "C"]).unwrap()),
+            ["B"]
+        );
+        assert!(dg
+            .articulation_points_between("leaf1", ["root"])
+            .unwrap()
+            .is_empty());
+        assert!(dg.articulation_points_between("root", ["Z"]).is_err());
+    }
+}
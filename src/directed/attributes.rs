@@ -0,0 +1,134 @@
+use crate::{prelude::*, utils::node_set::NodeVec};
+
+impl DirectedGraph {
+    /// Returns the value of the attribute `key` on `node` or `None`
+    /// if the node does not have that attribute.
+    pub fn node_attribute(
+        &self,
+        node: impl AsRef<str>,
+        key: impl AsRef<str>,
+    ) -> GraphInteractionResult<Option<&AttributeValue>> {
+        let node = self.get_internal(node)?;
+        Ok(self
+            .attributes
+            .as_ref()
+            .and_then(|attributes| attributes.get(node, key.as_ref())))
+    }
+
+    /// Returns every attribute set on `node` as `(key, value)` pairs.
+    pub fn node_attributes(
+        &self,
+        node: impl AsRef<str>,
+    ) -> GraphInteractionResult<Vec<(&str, &AttributeValue)>> {
+        let node = self.get_internal(node)?;
+        Ok(self
+            .attributes
+            .iter()
+            .flat_map(|attributes| attributes.get_all(node))
+            .collect())
+    }
+
+    /// Returns the nodes where the attribute `key` is equal to
+    /// `value`.
+    pub fn nodes_with_attribute(
+        &self,
+        key: impl AsRef<str>,
+        value: impl Into<AttributeValue>,
+    ) -> NodeVec {
+        let value = value.into();
+        let nodes = match &self.attributes {
+            Some(attributes) => self
+                .nodes
+                .iter()
+                .copied()
+                .filter(|&node| attributes.get(node, key.as_ref()) == Some(&value))
+                .collect(),
+            None => Vec::new(),
+        };
+        self.resolve_mul_slice(&nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn graph() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["A", "D"]).unwrap();
+        builder.set_node_attribute("A", "owner", "data-team");
+        builder.set_node_attribute("B", "owner", "ml-team");
+        builder.set_node_attribute("B", "rows", 100);
+        builder.set_node_attribute("C", "rows", 2.5);
+        builder.set_node_attribute("C", "public", true);
+        builder.set_node_attribute("C", "public", false);
+        builder.set_node_attribute("Z", "owner", "nobody");
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_node_attribute() {
+        let dg = graph();
+        assert_eq!(
+            dg.node_attribute("A", "owner").unwrap(),
+            Some(&AttributeValue::String("data-team".into()))
+        );
+        assert_eq!(
+            dg.node_attribute("B", "rows").unwrap().unwrap().as_int(),
+            Some(100)
+        );
+        assert_eq!(
+            dg.node_attribute("B", "rows").unwrap().unwrap().as_float(),
+            Some(100.0)
+        );
+        assert_eq!(
+            dg.node_attribute("C", "public").unwrap().unwrap().as_bool(),
+            Some(false)
+        );
+        assert_eq!(dg.node_attribute("D", "owner").unwrap(), None);
+        assert_eq!(dg.node_attribute("A", "missing").unwrap(), None);
+        // Attributes of nodes without edges are dropped
+        assert!(dg.node_attribute("Z", "owner").is_err());
+    }
+
+    #[test]
+    fn dg_node_attributes() {
+        let dg = graph();
+        assert_eq!(
+            dg.node_attributes("C").unwrap(),
+            [
+                ("rows", &AttributeValue::Float(2.5)),
+                ("public", &AttributeValue::Bool(false))
+            ]
+        );
+        assert!(dg.node_attributes("D").unwrap().is_empty());
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_edge("A", "B");
+        let dg = builder.build_directed();
+        assert!(dg.node_attributes("A").unwrap().is_empty());
+    }
+
+    #[test]
+    fn dg_nodes_with_attribute() {
+        let dg = graph();
+        assert_eq!(dg.nodes_with_attribute("owner", "ml-team"), ["B"]);
+        assert!(dg.nodes_with_attribute("owner", "nobody").is_empty());
+    }
+
+    #[test]
+    fn dg_subset_keeps_attributes() {
+        let dg = graph();
+        let dg2 = dg.subset("B").unwrap();
+        assert_eq!(
+            dg2.node_attribute("B", "rows").unwrap().unwrap().as_int(),
+            Some(100)
+        );
+        assert_eq!(dg2.node_attribute("A", "owner").unwrap(), None);
+
+        let dg3 = dg.subset_multi(["B", "D"]).unwrap();
+        assert_eq!(dg3.nodes_with_attribute("owner", "ml-team"), ["B"]);
+        assert!(dg3.nodes_with_attribute("owner", "data-team").is_empty());
+    }
+}
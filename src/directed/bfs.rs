@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+use fxhash::FxHashMap;
+
+use crate::utils::{node_map::NodeMap, sym::Sym};
+
+/// Scratch space for a bidirectional BFS. It only borrows the node
+/// maps while searching so one can be kept per rayon thread.
+#[derive(Default)]
+pub(crate) struct BidirectionalBfs {
+    /// Node -> the node it was reached from, going down
+    forward: FxHashMap<Sym, Sym>,
+    /// Node -> the node it was reached from, going up
+    backward: FxHashMap<Sym, Sym>,
+    forward_frontier: Vec<Sym>,
+    backward_frontier: Vec<Sym>,
+}
+
+impl BidirectionalBfs {
+    pub(crate) fn clear(&mut self) {
+        self.forward.clear();
+        self.backward.clear();
+        self.forward_frontier.clear();
+        self.backward_frontier.clear();
+    }
+
+    /// Finds a shortest path from any of `sources` to `to`. The
+    /// search grows from both ends at the same time, following
+    /// `children_map` down from the sources and `parent_map` up
+    /// from `to`, and stops when they meet.
+    ///
+    /// The path is placed in `path` and left empty if there is
+    /// none.
+    pub(crate) fn find_path(
+        &mut self,
+        children_map: &NodeMap,
+        parent_map: &NodeMap,
+        sources: &[Sym],
+        to: Sym,
+        path: &mut Vec<Sym>,
+    ) {
+        self.clear();
+        path.clear();
+        if sources.contains(&to) {
+            path.push(to);
+            return;
+        }
+
+        for &source in sources {
+            if self.forward.insert(source, source).is_none() {
+                self.forward_frontier.push(source);
+            }
+        }
+        self.backward.insert(to, to);
+        self.backward_frontier.push(to);
+
+        // Every level of the smaller frontier is expanded at once.
+        // The first node reached by both searches is always on a
+        // shortest path since both sides grow one level at a time.
+        let meeting = 'search: loop {
+            if self.forward_frontier.is_empty() || self.backward_frontier.is_empty() {
+                return;
+            }
+            let (frontier, map, neighbors, other) =
+                if self.forward_frontier.len() <= self.backward_frontier.len() {
+                    (
+                        &mut self.forward_frontier,
+                        &mut self.forward,
+                        children_map,
+                        &self.backward,
+                    )
+                } else {
+                    (
+                        &mut self.backward_frontier,
+                        &mut self.backward,
+                        parent_map,
+                        &self.forward,
+                    )
+                };
+            let level = frontier.len();
+            for i in 0..level {
+                let node = frontier[i];
+                for neighbor in neighbors.values(node) {
+                    if map.contains_key(&neighbor) {
+                        continue;
+                    }
+                    map.insert(neighbor, node);
+                    if other.contains_key(&neighbor) {
+                        break 'search neighbor;
+                    }
+                    frontier.push(neighbor);
+                }
+            }
+            frontier.drain(..level);
+        };
+
+        // The start and end of each side point to themselves
+        let mut current = meeting;
+        path.push(current);
+        while self.forward[&current] != current {
+            current = self.forward[&current];
+            path.push(current);
+        }
+        path.reverse();
+        current = meeting;
+        while self.backward[&current] != current {
+            current = self.backward[&current];
+            path.push(current);
+        }
+    }
+}
+
+/// Runs a BFS from `source` following `neighbors`, which is the
+/// children map to go down or the parent map to go up.
+/// `distances` must be filled with `usize::MAX`. Returns the
+/// visited nodes in the order they were reached.
+pub(crate) fn bfs_distances(
+    neighbors: &NodeMap,
+    source: Sym,
+    distances: &mut [usize],
+    mut on_edge: impl FnMut(Sym, Sym),
+) -> Vec<Sym> {
+    let mut order = vec![source];
+    let mut queue = VecDeque::new();
+    distances[source.into_usize()] = 0;
+    queue.push_back(source);
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[node.into_usize()];
+        for neighbor in neighbors.values(node) {
+            if distances[neighbor.into_usize()] == usize::MAX {
+                distances[neighbor.into_usize()] = distance + 1;
+                order.push(neighbor);
+                queue.push_back(neighbor);
+            }
+            if distances[neighbor.into_usize()] == distance + 1 {
+                on_edge(node, neighbor);
+            }
+        }
+    }
+    order
+}
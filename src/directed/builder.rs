@@ -1,6 +1,13 @@
 use std::sync::Arc;
 
-use crate::utils::{interner::InternerBuilder, node_map::NodeMap, sym::Sym};
+use crate::utils::{
+    edge_labels::EdgeLabels,
+    edge_map::EdgeMap,
+    interner::InternerBuilder,
+    node_attributes::{AttributeValue, NodeAttributes},
+    node_map::NodeMap,
+    sym::Sym,
+};
 use rayon::prelude::*;
 
 use super::{DirectedAcyclicGraph, DirectedGraph, GraphBuilderError, GraphHasCycle};
@@ -9,6 +16,16 @@ use super::{DirectedAcyclicGraph, DirectedGraph, GraphBuilderError, GraphHasCycl
 pub struct DirectedGraphBuilder {
     pub(crate) parents: Vec<Sym>,
     pub(crate) children: Vec<Sym>,
+    /// Weight of every edge, `None` for the ones added without a
+    /// weight. Only allocated once a weighted edge is added.
+    pub(crate) weights: Option<Vec<Option<f64>>>,
+    /// Node, attribute name and value
+    pub(crate) attributes: Vec<(Box<str>, Box<str>, AttributeValue)>,
+    /// Parent, child and label of every labeled edge
+    pub(crate) labels: Vec<(Sym, Sym, Sym)>,
+    pub(crate) label_interner: InternerBuilder,
+    /// Whether parallel edges are kept
+    pub(crate) multigraph: bool,
     pub(crate) interner: InternerBuilder,
 }
 
@@ -40,6 +57,11 @@ impl DirectedGraphBuilder {
             interner: InternerBuilder::new(),
             children: Vec::new(),
             parents: Vec::new(),
+            weights: None,
+            attributes: Vec::new(),
+            labels: Vec::new(),
+            label_interner: InternerBuilder::new(),
+            multigraph: false,
         }
     }
 
@@ -52,8 +74,92 @@ impl DirectedGraphBuilder {
         let to = self.get_or_intern(&to);
         self.parents.push(from);
         self.children.push(to);
+        if let Some(weights) = &mut self.weights {
+            weights.push(None);
+        }
+        self
+    }
+
+    /// Keeps parallel edges instead of collapsing repeated edges
+    /// into one. Every edge gets an id in the order it was added
+    /// and counts towards the number of edges of the graph.
+    ///
+    /// Every parallel edge keeps its own weight, see
+    /// [`DirectedGraph::parallel_edge_weights`]. Shortest paths
+    /// use the lowest weight between two nodes and flows add them
+    /// up.
+    ///
+    /// NOTE: Parallel edges share their labels.
+    pub fn set_multigraph(&mut self, multigraph: bool) -> &mut Self {
+        self.multigraph = multigraph;
+        self
+    }
+
+    /// Adds an edge with a weight (cost). Edges added through
+    /// [`DirectedGraphBuilder::add_edge`] have a weight of `1.0`.
+    ///
+    /// If the same edge is added more than once the last weight
+    /// is kept, unless the builder is a multigraph. Adding it again
+    /// without a weight does not reset it.
+    pub fn add_weighted_edge(
+        &mut self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+        weight: f64,
+    ) -> &mut Self {
+        let n_edges = self.parents.len();
+        self.add_edge(from, to);
+        let weights = self.weights.get_or_insert_with(|| vec![None; n_edges + 1]);
+        weights[n_edges] = Some(weight);
         self
     }
+
+    /// Adds an edge with a label (relationship type). The same
+    /// pair of nodes can be connected with many labels by adding
+    /// the edge once per label.
+    pub fn add_labeled_edge(
+        &mut self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+        label: impl AsRef<str>,
+    ) -> &mut Self {
+        self.add_edge(&from, &to);
+        self.add_edge_label(from, to, label)
+    }
+
+    /// Labels the edge `from -> to` without adding another edge,
+    /// so copying a labeled parallel edge keeps its multiplicity.
+    pub(crate) fn add_edge_label(
+        &mut self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+        label: impl AsRef<str>,
+    ) -> &mut Self {
+        let from = self.get_or_intern(from);
+        let to = self.get_or_intern(to);
+        let label = self.label_interner.get_or_intern(label);
+        self.labels.push((from, to, label));
+        self
+    }
+
+    /// Sets an attribute on a node. The attribute is kept through
+    /// subsets and serialization and can be queried with
+    /// [`DirectedGraph::node_attribute`].
+    ///
+    /// If the same attribute is set more than once the last value
+    /// is kept. Attributes of nodes that are not part of any edge
+    /// are dropped when the graph is built.
+    pub fn set_node_attribute(
+        &mut self,
+        node: impl AsRef<str>,
+        key: impl AsRef<str>,
+        value: impl Into<AttributeValue>,
+    ) -> &mut Self {
+        self.attributes
+            .push((node.as_ref().into(), key.as_ref().into(), value.into()));
+        self
+    }
+
     pub fn add_path(
         &mut self,
         path: impl IntoIterator<Item = impl AsRef<str>>,
@@ -133,6 +239,93 @@ impl DirectedGraphBuilder {
             }
         }
 
+        // On multigraphs the weight between two nodes is the lowest
+        // of its parallel edges, otherwise the last one set
+        let weights = self.weights.as_ref().map(|weights| {
+            let mut edge_weights = EdgeMap::default();
+            for ((&parent, &child), &weight) in self.parents.iter().zip(&self.children).zip(weights)
+            {
+                let weight = match weight {
+                    Some(weight) => weight,
+                    None if self.multigraph => 1.0,
+                    None => continue,
+                };
+                match edge_weights.get_mut(parent, child) {
+                    // NaN is kept so it is still reported
+                    Some(current) if self.multigraph => {
+                        if weight < *current || weight.is_nan() {
+                            *current = weight;
+                        }
+                    }
+                    Some(current) => *current = weight,
+                    None => {
+                        edge_weights.insert(parent, child, weight);
+                    }
+                }
+            }
+            edge_weights
+        });
+
+        let attributes = (!self.attributes.is_empty()).then(|| {
+            let mut attributes = NodeAttributes::default();
+            for (node, key, value) in self.attributes {
+                if let Some(node) = interner.get(&node) {
+                    attributes.insert(interner.len(), node, &key, value);
+                }
+            }
+            attributes
+        });
+
+        let labels = (!self.labels.is_empty()).then(|| {
+            let mut labels = EdgeMap::<Vec<Sym>>::default();
+            for (parent, child, label) in self.labels {
+                match labels.get_mut(parent, child) {
+                    Some(edge_labels) => edge_labels.push(label),
+                    None => {
+                        labels.insert(parent, child, vec![label]);
+                    }
+                }
+            }
+            labels.values_mut().for_each(|edge_labels| {
+                edge_labels.sort_unstable();
+                edge_labels.dedup();
+            });
+            EdgeLabels {
+                interner: Arc::new(self.label_interner.build()),
+                labels,
+            }
+        });
+
+        let edge_ids = self.multigraph.then(|| {
+            let mut edge_ids = EdgeMap::<Vec<u32>>::default();
+            for (id, (&parent, &child)) in self.parents.iter().zip(&self.children).enumerate() {
+                let id = id as u32;
+                match edge_ids.get_mut(parent, child) {
+                    Some(ids) => ids.push(id),
+                    None => {
+                        edge_ids.insert(parent, child, vec![id]);
+                    }
+                }
+            }
+            n_edges = self.parents.len();
+            edge_ids
+        });
+
+        let parallel_weights = match (&edge_ids, &self.weights) {
+            (Some(edge_ids), Some(weights)) => {
+                let mut parallel_weights = EdgeMap::<Vec<f64>>::default();
+                for (parent, child, ids) in edge_ids.iter() {
+                    let edge_weights = ids
+                        .iter()
+                        .map(|&id| weights[id as usize].unwrap_or(1.0))
+                        .collect();
+                    parallel_weights.insert(parent, child, edge_weights);
+                }
+                Some(parallel_weights)
+            }
+            _ => None,
+        };
+
         DirectedGraph {
             interner,
             leaves,
@@ -141,6 +334,11 @@ impl DirectedGraphBuilder {
             children_map,
             parent_map,
             n_edges,
+            weights,
+            attributes,
+            labels,
+            edge_ids,
+            parallel_weights,
             buf: Default::default(),
         }
     }
@@ -0,0 +1,288 @@
+use rayon::prelude::*;
+
+use super::bfs::bfs_distances;
+use crate::{prelude::*, utils::sym::Sym};
+
+/// Returns how many nodes have each degree. `counts[d]` is the
+/// number of nodes with a degree of `d`.
+fn degree_distribution(nodes: &[Sym], degree: impl Fn(Sym) -> usize) -> Vec<usize> {
+    let mut counts = Vec::new();
+    for &node in nodes {
+        let degree = degree(node);
+        if counts.len() <= degree {
+            counts.resize(degree + 1, 0);
+        }
+        counts[degree] += 1;
+    }
+    counts
+}
+
+impl DirectedGraph {
+    fn with_names(&self, scores: &[f64]) -> Vec<(&str, f64)> {
+        self.nodes
+            .iter()
+            .map(|&node| (self.resolve(node), scores[node.into_usize()]))
+            .collect()
+    }
+
+    /// Returns how many nodes have each in-degree. `counts[d]` is
+    /// the number of nodes with `d` edges entering them, see
+    /// [`DirectedGraph::in_degree`].
+    pub fn in_degree_distribution(&self) -> Vec<usize> {
+        degree_distribution(&self.nodes, |node| self.in_degree_u32(node))
+    }
+
+    /// Returns how many nodes have each out-degree. `counts[d]` is
+    /// the number of nodes with `d` edges leaving them, see
+    /// [`DirectedGraph::out_degree`].
+    pub fn out_degree_distribution(&self) -> Vec<usize> {
+        degree_distribution(&self.nodes, |node| self.out_degree_u32(node))
+    }
+
+    /// Computes the PageRank of every node. Rank flows from
+    /// parents to children and the rank of leaves is spread
+    /// evenly over the whole graph.
+    ///
+    /// Stops after `max_iterations` or once the total change
+    /// between iterations is below `tolerance`. The ranks add
+    /// up to `1.0`.
+    pub fn pagerank(
+        &self,
+        damping: f64,
+        max_iterations: usize,
+        tolerance: f64,
+    ) -> Vec<(&str, f64)> {
+        let n_nodes = self.nodes.len();
+        if n_nodes == 0 {
+            return Vec::new();
+        }
+        let (children_map, parent_map) = (&self.children_map, &self.parent_map);
+        let base = (1.0 - damping) / n_nodes as f64;
+        let mut ranks = vec![0.0; self.interner.len()];
+        for &node in &self.nodes {
+            ranks[node.into_usize()] = 1.0 / n_nodes as f64;
+        }
+
+        for _ in 0..max_iterations {
+            let dangling: f64 = self
+                .nodes
+                .iter()
+                .filter(|&&node| children_map.n_values(node) == 0)
+                .map(|node| ranks[node.into_usize()])
+                .sum();
+            let dangling = damping * dangling / n_nodes as f64;
+
+            let new_ranks: Vec<f64> = self
+                .nodes
+                .par_iter()
+                .map(|&node| {
+                    let incoming: f64 = parent_map
+                        .values(node)
+                        .map(|parent| {
+                            ranks[parent.into_usize()] / children_map.n_values(parent) as f64
+                        })
+                        .sum();
+                    base + dangling + damping * incoming
+                })
+                .collect();
+
+            let mut change = 0.0;
+            for (&node, rank) in self.nodes.iter().zip(new_ranks) {
+                change += (ranks[node.into_usize()] - rank).abs();
+                ranks[node.into_usize()] = rank;
+            }
+            if change < tolerance {
+                break;
+            }
+        }
+
+        self.with_names(&ranks)
+    }
+
+    /// Computes the betweenness centrality of every node using
+    /// Brandes' algorithm: the number of shortest paths between
+    /// other nodes that go through it. Edge weights are ignored.
+    ///
+    /// If `normalized` is `true` the scores are divided by
+    /// `(n - 1) * (n - 2)`.
+    pub fn betweenness_centrality(&self, normalized: bool) -> Vec<(&str, f64)> {
+        let n_symbols = self.interner.len();
+        let children_map = &self.children_map;
+
+        let mut scores = self
+            .nodes
+            .par_iter()
+            .fold(
+                || vec![0.0; n_symbols],
+                |mut scores, &source| {
+                    let mut distances = vec![usize::MAX; n_symbols];
+                    let mut n_paths = vec![0.0; n_symbols];
+                    let mut predecessors = vec![Vec::new(); n_symbols];
+                    n_paths[source.into_usize()] = 1.0;
+                    let order = bfs_distances(children_map, source, &mut distances, |p, c| {
+                        n_paths[c.into_usize()] += n_paths[p.into_usize()];
+                        predecessors[c.into_usize()].push(p);
+                    });
+
+                    let mut dependency = vec![0.0; n_symbols];
+                    for &node in order.iter().rev() {
+                        for &pred in &predecessors[node.into_usize()] {
+                            dependency[pred.into_usize()] += n_paths[pred.into_usize()]
+                                / n_paths[node.into_usize()]
+                                * (1.0 + dependency[node.into_usize()]);
+                        }
+                        if node != source {
+                            scores[node.into_usize()] += dependency[node.into_usize()];
+                        }
+                    }
+                    scores
+                },
+            )
+            .reduce(
+                || vec![0.0; n_symbols],
+                |mut a, b| {
+                    a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                    a
+                },
+            );
+
+        let n_nodes = self.nodes.len() as f64;
+        if normalized && n_nodes > 2.0 {
+            let scale = 1.0 / ((n_nodes - 1.0) * (n_nodes - 2.0));
+            scores.iter_mut().for_each(|score| *score *= scale);
+        }
+
+        self.with_names(&scores)
+    }
+
+    /// Computes the closeness centrality of every node based on
+    /// the distance to the nodes it can reach. Edge weights are
+    /// ignored.
+    ///
+    /// Uses the Wasserman and Faust formula so nodes that reach
+    /// only part of the graph score lower. Leaves have a score
+    /// of `0.0`.
+    pub fn closeness_centrality(&self) -> Vec<(&str, f64)> {
+        let n_symbols = self.interner.len();
+        let n_nodes = self.nodes.len();
+        let children_map = &self.children_map;
+
+        let closeness: Vec<f64> = self
+            .nodes
+            .par_iter()
+            .map(|&source| {
+                let mut distances = vec![usize::MAX; n_symbols];
+                let reached = bfs_distances(children_map, source, &mut distances, |_, _| {});
+                let total: usize = reached
+                    .iter()
+                    .map(|node| distances[node.into_usize()])
+                    .sum();
+                let n_reached = (reached.len() - 1) as f64;
+                if total == 0 {
+                    return 0.0;
+                }
+                (n_reached / total as f64) * (n_reached / (n_nodes - 1) as f64)
+            })
+            .collect();
+
+        self.nodes
+            .iter()
+            .zip(closeness)
+            .map(|(&node, score)| (self.resolve(node), score))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn score(scores: &[(&str, f64)], node: &str) -> f64 {
+        scores
+            .iter()
+            .find(|(name, _)| *name == node)
+            .map(|(_, score)| *score)
+            .unwrap()
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{a} != {b}");
+    }
+
+    #[test]
+    fn dg_degree_distributions() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["A", "C"]).unwrap();
+        builder.add_path(["A", "D"]).unwrap();
+        let dg = builder.build_directed();
+        // A: 0 parents, B: 1, C: 2, D: 1
+        assert_eq!(dg.in_degree_distribution(), [1, 2, 1]);
+        // A: 3 children, B: 1, C: 0, D: 0
+        assert_eq!(dg.out_degree_distribution(), [2, 1, 0, 1]);
+    }
+
+    #[test]
+    fn dg_pagerank() {
+        // Every node links to the next one, so every node ends up
+        // with the same rank
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "A"]).unwrap();
+        let dg = builder.build_directed();
+        let ranks = dg.pagerank(0.85, 100, 1e-10);
+        for (_, rank) in &ranks {
+            assert_close(*rank, 1.0 / 3.0);
+        }
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "C"]).unwrap();
+        builder.add_path(["B", "C"]).unwrap();
+        builder.add_path(["C", "D"]).unwrap();
+        let dg = builder.build_directed();
+        let ranks = dg.pagerank(0.85, 100, 1e-10);
+        assert_close(ranks.iter().map(|(_, rank)| rank).sum(), 1.0);
+        assert!(score(&ranks, "C") > score(&ranks, "A"));
+        assert!(score(&ranks, "D") > score(&ranks, "C"));
+        assert_close(score(&ranks, "A"), score(&ranks, "B"));
+    }
+
+    #[test]
+    fn dg_betweenness_centrality() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "D"]).unwrap();
+        builder.add_path(["A", "E", "D"]).unwrap();
+        let dg = builder.build_directed();
+        let scores = dg.betweenness_centrality(false);
+        // A -> D goes through E, the path through B and C is longer
+        assert_close(score(&scores, "B"), 1.0);
+        assert_close(score(&scores, "C"), 1.0);
+        assert_close(score(&scores, "E"), 1.0);
+        assert_close(score(&scores, "A"), 0.0);
+        assert_close(score(&scores, "D"), 0.0);
+
+        let normalized = dg.betweenness_centrality(true);
+        assert_close(score(&normalized, "B"), 1.0 / 12.0);
+
+        // Both shortest paths from A to D are counted
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "D"]).unwrap();
+        builder.add_path(["A", "C", "D"]).unwrap();
+        let dg = builder.build_directed();
+        let scores = dg.betweenness_centrality(false);
+        assert_close(score(&scores, "B"), 0.5);
+        assert_close(score(&scores, "C"), 0.5);
+    }
+
+    #[test]
+    fn dg_closeness_centrality() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["A", "C"]).unwrap();
+        let dg = builder.build_directed();
+        let scores = dg.closeness_centrality();
+        assert_close(score(&scores, "A"), 1.0);
+        // B only reaches C at a distance of 1
+        assert_close(score(&scores, "B"), 0.5);
+        assert_close(score(&scores, "C"), 0.0);
+    }
+}
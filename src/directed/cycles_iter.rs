@@ -0,0 +1,348 @@
+use fxhash::{FxHashMap, FxHashSet};
+
+use crate::{
+    prelude::*,
+    utils::{node_set::NodeVec, sym::Sym},
+};
+
+/// Lazily enumerates the simple cycles of a graph using Johnson's
+/// algorithm. Created by [`DirectedGraph::simple_cycles`] and
+/// [`DirectedGraph::cycles_through`].
+///
+/// Every cycle is yielded once, starting on any of its nodes. The
+/// first node is not repeated at the end.
+pub struct CyclesIter<'a> {
+    graph: &'a DirectedGraph,
+    /// Nodes with an edge to themselves left to yield
+    self_loops: Vec<Sym>,
+    /// Strongly connected components left to search
+    components: Vec<Vec<Sym>>,
+    /// If `true` only cycles through the first start node are
+    /// searched
+    only_first_start: bool,
+    /// Nodes of the component being searched
+    members: Vec<Sym>,
+    in_component: Vec<bool>,
+    start: Sym,
+    path: Vec<Sym>,
+    blocked: FxHashSet<Sym>,
+    closed: FxHashSet<Sym>,
+    blocked_by: FxHashMap<Sym, Vec<Sym>>,
+    /// Node on the path and the neighbors left to visit
+    stack: Vec<(Sym, Vec<Sym>)>,
+    max_cycles: Option<usize>,
+    max_length: Option<usize>,
+    n_found: usize,
+}
+
+impl<'a> CyclesIter<'a> {
+    fn new(graph: &'a DirectedGraph) -> Self {
+        CyclesIter {
+            graph,
+            self_loops: Vec::new(),
+            components: Vec::new(),
+            only_first_start: false,
+            members: Vec::new(),
+            in_component: vec![false; graph.interner.len()],
+            start: Sym::RESERVED,
+            path: Vec::new(),
+            blocked: FxHashSet::default(),
+            closed: FxHashSet::default(),
+            blocked_by: FxHashMap::default(),
+            stack: Vec::new(),
+            max_cycles: None,
+            max_length: None,
+            n_found: 0,
+        }
+    }
+
+    fn all(graph: &'a DirectedGraph) -> Self {
+        let mut iter = CyclesIter::new(graph);
+        iter.self_loops = graph
+            .nodes
+            .iter()
+            .copied()
+            .filter(|&node| graph.has_edge_u32(node, node))
+            .collect();
+        iter.components = graph
+            .strongly_connected_components_u32()
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .collect();
+        iter
+    }
+
+    fn through(graph: &'a DirectedGraph, node: Sym) -> Self {
+        let mut iter = CyclesIter::new(graph);
+        if graph.has_edge_u32(node, node) {
+            iter.self_loops.push(node);
+        }
+        let component = graph
+            .strongly_connected_components_u32()
+            .into_iter()
+            .find(|component| component.contains(&node));
+        if let Some(mut component) = component.filter(|component| component.len() > 1) {
+            // The start node is always the last one
+            let i = component.iter().position(|&member| member == node);
+            if let Some(i) = i {
+                let last = component.len() - 1;
+                component.swap(i, last);
+            }
+            iter.components.push(component);
+            iter.only_first_start = true;
+        }
+        iter
+    }
+
+    /// Stops after `max_cycles` cycles have been found.
+    pub fn max_cycles(mut self, max_cycles: usize) -> Self {
+        self.max_cycles = Some(max_cycles);
+        self
+    }
+
+    /// Only yields cycles with at most `max_length` edges.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    fn neighbors(&self, node: Sym) -> Vec<Sym> {
+        self.graph
+            .children_map
+            .values(node)
+            .filter(|&child| child != node && self.in_component[child.into_usize()])
+            .collect()
+    }
+
+    fn unblock(&mut self, node: Sym) {
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            if self.blocked.remove(&node) {
+                if let Some(blocked_by) = self.blocked_by.get_mut(&node) {
+                    stack.append(blocked_by);
+                }
+            }
+        }
+    }
+
+    /// Starts the search for the cycles going through the last
+    /// node of `component`.
+    fn start_search(&mut self, component: Vec<Sym>) {
+        for &member in &self.members {
+            self.in_component[member.into_usize()] = false;
+        }
+        for &member in &component {
+            self.in_component[member.into_usize()] = true;
+        }
+        self.members = component;
+        self.start = self.members[self.members.len() - 1];
+        self.path.clear();
+        self.path.push(self.start);
+        self.blocked.clear();
+        self.blocked.insert(self.start);
+        self.closed.clear();
+        self.blocked_by.clear();
+        self.stack.push((self.start, self.neighbors(self.start)));
+    }
+
+    /// Queues the strongly connected components left after
+    /// removing the start node of the last search.
+    fn queue_remaining_components(&mut self) {
+        self.in_component[self.start.into_usize()] = false;
+        let remaining = &self.members[..self.members.len() - 1];
+        let components = self
+            .graph
+            .strongly_connected_components_within_u32(remaining, |node| {
+                self.in_component[node.into_usize()]
+            });
+        self.components.extend(
+            components
+                .into_iter()
+                .filter(|component| component.len() > 1),
+        );
+        for member in self.members.drain(..) {
+            self.in_component[member.into_usize()] = false;
+        }
+    }
+}
+
+impl Iterator for CyclesIter<'_> {
+    type Item = NodeVec;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self
+            .max_cycles
+            .is_some_and(|max_cycles| self.n_found >= max_cycles)
+            || self.max_length == Some(0)
+        {
+            return None;
+        }
+
+        if let Some(node) = self.self_loops.pop() {
+            self.n_found += 1;
+            return Some(self.graph.resolve_mul_slice(&[node]));
+        }
+
+        loop {
+            while let Some((node, neighbors)) = self.stack.last_mut() {
+                let node = *node;
+                if let Some(next) = neighbors.pop() {
+                    if next == self.start {
+                        self.closed.extend(self.path.iter().copied());
+                        self.n_found += 1;
+                        return Some(self.graph.resolve_mul_slice(&self.path));
+                    }
+                    if self.blocked.contains(&next) {
+                        continue;
+                    }
+                    // Any cycle through `next` would be too long.
+                    // The path is closed so its nodes are unblocked
+                    // and can still be used by shorter cycles.
+                    if self
+                        .max_length
+                        .is_some_and(|max_length| self.path.len() >= max_length)
+                    {
+                        self.closed.extend(self.path.iter().copied());
+                        continue;
+                    }
+                    self.path.push(next);
+                    self.stack.push((next, self.neighbors(next)));
+                    self.closed.remove(&next);
+                    self.blocked.insert(next);
+                    continue;
+                }
+
+                if self.closed.contains(&node) {
+                    self.unblock(node);
+                } else {
+                    for neighbor in self.neighbors(node) {
+                        let blocked_by = self.blocked_by.entry(neighbor).or_default();
+                        if !blocked_by.contains(&node) {
+                            blocked_by.push(node);
+                        }
+                    }
+                }
+                self.stack.pop();
+                self.path.pop();
+            }
+
+            if !self.members.is_empty() {
+                if self.only_first_start {
+                    return None;
+                }
+                self.queue_remaining_components();
+            }
+
+            let component = self.components.pop()?;
+            self.start_search(component);
+        }
+    }
+}
+
+impl DirectedGraph {
+    /// Returns an iterator over every simple cycle in the graph.
+    /// Cycles are generated on demand, use
+    /// [`CyclesIter::max_cycles`] and [`CyclesIter::max_length`]
+    /// to bound the search.
+    pub fn simple_cycles(&self) -> CyclesIter<'_> {
+        CyclesIter::all(self)
+    }
+
+    /// Returns an iterator over every simple cycle that goes
+    /// through `node`. Every cycle starts on `node`.
+    pub fn cycles_through(&self, node: impl AsRef<str>) -> GraphInteractionResult<CyclesIter<'_>> {
+        let node = self.get_internal(node)?;
+        Ok(CyclesIter::through(self, node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    /// Rotates every cycle so it starts on its smallest node and
+    /// sorts them.
+    fn normalize(cycles: impl Iterator<Item = NodeVec>) -> Vec<Vec<String>> {
+        let mut cycles: Vec<Vec<String>> = cycles
+            .map(|cycle| {
+                let mut cycle: Vec<String> = cycle.iter().map(|node| node.to_string()).collect();
+                let min = (0..cycle.len()).min_by_key(|&i| &cycle[i]).unwrap_or(0);
+                cycle.rotate_left(min);
+                cycle
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    fn graph() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "A"]).unwrap();
+        builder.add_path(["B", "A"]).unwrap();
+        builder.add_path(["C", "D", "B"]).unwrap();
+        builder.add_path(["D", "D"]).unwrap();
+        builder.add_path(["D", "E"]).unwrap();
+        builder.add_path(["X", "Y", "X"]).unwrap();
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_simple_cycles() {
+        let dg = graph();
+        assert_eq!(
+            normalize(dg.simple_cycles()),
+            vec![
+                vec!["A", "B"],
+                vec!["A", "B", "C"],
+                vec!["B", "C", "D"],
+                vec!["D"],
+                vec!["X", "Y"],
+            ]
+        );
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        assert_eq!(builder.build_directed().simple_cycles().count(), 0);
+    }
+
+    #[test]
+    fn dg_simple_cycles_complete_graph() {
+        // A complete graph with `n` nodes has the sum of
+        // `C(n, k) * (k - 1)!` cycles for `k` in `2..=n`
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..5 {
+            for j in 0..5 {
+                if i != j {
+                    builder.add_edge(i.to_string(), j.to_string());
+                }
+            }
+        }
+        let dg = builder.build_directed();
+        assert_eq!(dg.simple_cycles().count(), 10 + 20 + 30 + 24);
+        assert_eq!(dg.simple_cycles().max_length(2).count(), 10);
+        assert_eq!(dg.simple_cycles().max_length(3).count(), 30);
+        assert_eq!(dg.simple_cycles().max_cycles(7).count(), 7);
+        assert_eq!(dg.simple_cycles().max_length(0).count(), 0);
+
+        let mut cycles = dg.simple_cycles();
+        assert_eq!(cycles.by_ref().count(), 84);
+        assert_eq!(cycles.next(), None);
+    }
+
+    #[test]
+    fn dg_cycles_through() {
+        let dg = graph();
+        let cycles: Vec<NodeVec> = dg.cycles_through("B").unwrap().collect();
+        assert!(cycles.iter().all(|cycle| cycle.get(0) == Some("B")));
+        assert_eq!(
+            normalize(cycles.into_iter()),
+            vec![vec!["A", "B"], vec!["A", "B", "C"], vec!["B", "C", "D"]]
+        );
+        assert_eq!(
+            normalize(dg.cycles_through("D").unwrap()),
+            vec![vec!["B", "C", "D"], vec!["D"]]
+        );
+        assert_eq!(dg.cycles_through("E").unwrap().count(), 0);
+        assert_eq!(dg.cycles_through("B").unwrap().max_length(2).count(), 1);
+        assert!(dg.cycles_through("Z").is_err());
+    }
+}
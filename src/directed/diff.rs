@@ -0,0 +1,256 @@
+use fxhash::FxHashSet;
+
+use crate::{prelude::*, utils::sym::Sym};
+
+/// Structural changes between two versions of a graph. Created by
+/// [`DirectedGraph::diff`] and applied with
+/// [`DirectedGraph::apply_diff`].
+///
+/// Nodes and edges are stored by name and sorted, so a diff does
+/// not depend on the interners of the graphs it was created from.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphDiff {
+    pub added_nodes: Vec<Box<str>>,
+    pub removed_nodes: Vec<Box<str>>,
+    pub added_edges: Vec<(Box<str>, Box<str>)>,
+    pub removed_edges: Vec<(Box<str>, Box<str>)>,
+    /// Nodes present in both versions whose parents or children
+    /// changed
+    pub changed_nodes: Vec<Box<str>>,
+}
+
+impl GraphDiff {
+    /// Returns `true` if both versions have the same nodes and
+    /// edges.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+impl DirectedGraph {
+    /// Returns the symbol of `node` if it is part of this graph.
+    fn get_node_by_name(&self, node: &str) -> Option<Sym> {
+        self.interner
+            .get(node)
+            .filter(|sym| self.nodes.binary_search(sym).is_ok())
+    }
+
+    /// Returns the edges of `self` missing in `other` sorted by
+    /// name.
+    fn missing_edges(&self, other: &DirectedGraph) -> Vec<(Box<str>, Box<str>)> {
+        let mut edges: Vec<(Box<str>, Box<str>)> = self
+            .edges_u32()
+            .map(|(parent, child)| (self.resolve(parent), self.resolve(child)))
+            .filter(|&(parent, child)| !other.has_edge_by_name(parent, child))
+            .map(|(parent, child)| (parent.into(), child.into()))
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Returns the nodes of `self` missing in `other` sorted by
+    /// name.
+    fn missing_nodes(&self, other: &DirectedGraph) -> Vec<Box<str>> {
+        let mut nodes: Vec<Box<str>> = self
+            .nodes
+            .iter()
+            .map(|&node| self.resolve(node))
+            .filter(|node| other.get_node_by_name(node).is_none())
+            .map(Into::into)
+            .collect();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    /// Compares two versions of a graph by node name.
+    pub fn diff(old: &DirectedGraph, new: &DirectedGraph) -> GraphDiff {
+        let added_nodes = new.missing_nodes(old);
+        let removed_nodes = old.missing_nodes(new);
+        let added_edges = new.missing_edges(old);
+        let removed_edges = old.missing_edges(new);
+
+        let mut changed_nodes: Vec<Box<str>> = added_edges
+            .iter()
+            .chain(&removed_edges)
+            .flat_map(|(parent, child)| [parent, child])
+            .filter(|node| {
+                old.get_node_by_name(node).is_some() && new.get_node_by_name(node).is_some()
+            })
+            .cloned()
+            .collect();
+        changed_nodes.sort_unstable();
+        changed_nodes.dedup();
+
+        GraphDiff {
+            added_nodes,
+            removed_nodes,
+            added_edges,
+            removed_edges,
+            changed_nodes,
+        }
+    }
+
+    /// Returns a new graph with `diff` applied as a patch. Removed
+    /// nodes take every edge connected to them.
+    ///
+    /// Weights, labels and attributes of the edges and nodes that
+    /// are kept are preserved. Changes that are already present in
+    /// the graph are ignored.
+    ///
+    /// The result is a multigraph if `self` is one. Kept parallel
+    /// edges keep their weights, a removed edge takes every
+    /// parallel edge between its nodes and an added edge is added
+    /// once.
+    pub fn apply_diff(&self, diff: &GraphDiff) -> DirectedGraph {
+        let removed_nodes: FxHashSet<&str> = diff.removed_nodes.iter().map(|n| &**n).collect();
+        let removed_edges: FxHashSet<(&str, &str)> = diff
+            .removed_edges
+            .iter()
+            .map(|(parent, child)| (&**parent, &**child))
+            .collect();
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.set_multigraph(self.is_multigraph());
+        for (parent, child) in self.edges_u32() {
+            let edge = (self.resolve(parent), self.resolve(child));
+            if removed_nodes.contains(edge.0)
+                || removed_nodes.contains(edge.1)
+                || removed_edges.contains(&edge)
+            {
+                continue;
+            }
+            self.copy_edge_into(&mut builder, parent, child);
+        }
+        for (parent, child) in &diff.added_edges {
+            if !self.has_edge_by_name(parent, child) {
+                builder.add_edge(parent, child);
+            }
+        }
+        self.copy_attributes_into(&mut builder);
+        builder.build_directed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn versions() -> (DirectedGraph, DirectedGraph) {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["A", "D"]).unwrap();
+        builder.add_path(["D", "E"]).unwrap();
+        let old = builder.build_directed();
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["B", "D"]).unwrap();
+        builder.add_path(["C", "F"]).unwrap();
+        let new = builder.build_directed();
+
+        (old, new)
+    }
+
+    fn names(names: &[&str]) -> Vec<Box<str>> {
+        names.iter().map(|&name| name.into()).collect()
+    }
+
+    fn edges(edges: &[(&str, &str)]) -> Vec<(Box<str>, Box<str>)> {
+        edges
+            .iter()
+            .map(|&(parent, child)| (parent.into(), child.into()))
+            .collect()
+    }
+
+    #[test]
+    fn dg_diff() {
+        let (old, new) = versions();
+        let diff = DirectedGraph::diff(&old, &new);
+        assert_eq!(diff.added_nodes, names(&["F"]));
+        assert_eq!(diff.removed_nodes, names(&["E"]));
+        assert_eq!(diff.added_edges, edges(&[("B", "D"), ("C", "F")]));
+        assert_eq!(diff.removed_edges, edges(&[("A", "D"), ("D", "E")]));
+        assert_eq!(diff.changed_nodes, names(&["A", "B", "C", "D"]));
+        assert!(!diff.is_empty());
+
+        assert!(DirectedGraph::diff(&old, &old).is_empty());
+        assert_eq!(DirectedGraph::diff(&old, &old), GraphDiff::default());
+    }
+
+    #[test]
+    fn dg_diff_subset_shares_interner() {
+        let (old, _) = versions();
+        let subset = old.subset("D").unwrap();
+        let diff = DirectedGraph::diff(&old, &subset);
+        assert_eq!(diff.removed_nodes, names(&["A", "B", "C"]));
+        assert!(diff.added_nodes.is_empty());
+        assert_eq!(diff.changed_nodes, names(&["D"]));
+    }
+
+    #[test]
+    fn dg_apply_diff() {
+        let (old, new) = versions();
+        let diff = DirectedGraph::diff(&old, &new);
+        let patched = old.apply_diff(&diff);
+        assert!(DirectedGraph::diff(&patched, &new).is_empty());
+
+        // Applying the same patch twice does nothing
+        let patched_twice = patched.apply_diff(&diff);
+        assert!(DirectedGraph::diff(&patched_twice, &new).is_empty());
+    }
+
+    #[test]
+    fn dg_apply_diff_keeps_weights() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 4.0);
+        builder.add_edge("B", "C");
+        let old = builder.build_directed();
+
+        let diff = GraphDiff {
+            added_edges: edges(&[("C", "D")]),
+            removed_edges: edges(&[("B", "C")]),
+            ..Default::default()
+        };
+        let patched = old.apply_diff(&diff);
+        assert_eq!(patched.edge_weight("A", "B").unwrap(), Some(4.0));
+        assert_eq!(patched.edge_weight("C", "D").unwrap(), Some(1.0));
+        assert_eq!(patched.edge_weight("B", "C").unwrap(), None);
+    }
+
+    #[test]
+    fn dg_apply_diff_keeps_parallel_edges() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.set_multigraph(true);
+        builder.add_weighted_edge("A", "B", 4.0);
+        builder.add_weighted_edge("A", "B", 2.0);
+        builder.add_edge("B", "C");
+        builder.add_edge("B", "C");
+        let old = builder.build_directed();
+
+        let diff = GraphDiff {
+            added_edges: edges(&[("C", "D")]),
+            removed_edges: edges(&[("B", "C")]),
+            ..Default::default()
+        };
+        let patched = old.apply_diff(&diff);
+        assert!(patched.is_multigraph());
+        assert_eq!(patched.n_edges(), 3);
+        assert_eq!(patched.parallel_edge_weights("A", "B").unwrap(), [4.0, 2.0]);
+        assert_eq!(patched.edge_multiplicity("B", "C").unwrap(), 0);
+        assert_eq!(patched.edge_multiplicity("C", "D").unwrap(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn dg_diff_serde() {
+        let (old, new) = versions();
+        let diff = DirectedGraph::diff(&old, &new);
+        let json = serde_json::to_string(&diff).unwrap();
+        let de_diff: GraphDiff = serde_json::from_str(&json).unwrap();
+        assert_eq!(diff, de_diff);
+    }
+}
@@ -0,0 +1,203 @@
+use super::flow::FlowNetwork;
+use crate::{prelude::*, utils::node_set::NodeVec};
+
+/// Kind of independence required by
+/// [`DirectedGraph::disjoint_paths`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisjointPathsKind {
+    /// Paths do not share any node other than `from` and `to`
+    Node,
+    /// Paths do not share any edge
+    Edge,
+}
+
+impl DirectedGraph {
+    /// Builds a network where every edge has a capacity of one.
+    /// For node-disjoint paths every node `v` is split into `v`,
+    /// which receives the incoming edges, and `v + n`, which holds
+    /// the outgoing edges, joined by an edge with a capacity of
+    /// one.
+    fn disjoint_paths_network_u32(&self, kind: DisjointPathsKind) -> FlowNetwork {
+        let n_symbols = self.interner.len() as u32;
+        match kind {
+            DisjointPathsKind::Edge => {
+                let mut network = FlowNetwork::new(self.interner.len());
+                for (parent, child) in self.edges_u32() {
+                    network.add_edge(parent, child, 1.0);
+                }
+                network
+            }
+            DisjointPathsKind::Node => {
+                let mut network = FlowNetwork::new(2 * self.interner.len());
+                for &node in &self.nodes {
+                    network.add_edge(node, node + n_symbols, 1.0);
+                }
+                for (parent, child) in self.edges_u32() {
+                    network.add_edge(parent + n_symbols, child, 1.0);
+                }
+                network
+            }
+        }
+    }
+
+    /// Returns a maximum set of paths between two nodes that do
+    /// not share any node or edge, depending on `kind`. The number
+    /// of paths is the number of independent routes between the
+    /// nodes.
+    ///
+    /// NOTE: Weights are ignored and parallel edges of a
+    /// multigraph count as one edge.
+    pub fn disjoint_paths(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+        kind: DisjointPathsKind,
+    ) -> GraphInteractionResult<Vec<NodeVec>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+        if from == to {
+            return Ok(vec![self.resolve_mul_slice(&[from])]);
+        }
+
+        let n_symbols = self.interner.len() as u32;
+        let mut network = self.disjoint_paths_network_u32(kind);
+        let source = match kind {
+            DisjointPathsKind::Edge => from,
+            DisjointPathsKind::Node => from + n_symbols,
+        };
+        let n_paths = network.max_flow(source, to).round() as usize;
+
+        // Flow left to follow on every edge of the network. Only
+        // even edges are part of the graph, odd ones are their
+        // reverse.
+        let mut remaining: Vec<bool> = (0..network.to.len())
+            .map(|edge| edge % 2 == 0 && network.flow(edge) > 0.5)
+            .collect();
+
+        let mut paths = Vec::with_capacity(n_paths);
+        for _ in 0..n_paths {
+            let mut path = vec![from];
+            let mut node = source;
+            while node != to {
+                let edge = network.adjacency[node.into_usize()]
+                    .iter()
+                    .copied()
+                    .find(|&edge| remaining[edge]);
+                let Some(edge) = edge else {
+                    break;
+                };
+                remaining[edge] = false;
+                node = network.to[edge];
+                // Split nodes are only added once, when they are
+                // entered
+                if node.into_usize() >= n_symbols as usize {
+                    continue;
+                }
+                // The flow may go around a cycle, which is not
+                // part of any path
+                match path.iter().position(|&visited| visited == node) {
+                    Some(i) => path.truncate(i + 1),
+                    None => path.push(node),
+                }
+            }
+            paths.push(self.resolve_mul_slice(&path));
+        }
+
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn sorted(paths: Vec<NodeVec>) -> Vec<Vec<String>> {
+        let mut paths: Vec<Vec<String>> = paths
+            .iter()
+            .map(|path| path.iter().map(|node| node.to_string()).collect())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    /// Two routes that share the node `M` but no edges
+    fn bowtie() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["S", "A", "M", "C", "T"]).unwrap();
+        builder.add_path(["S", "B", "M", "D", "T"]).unwrap();
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_edge_disjoint_paths() {
+        let dg = bowtie();
+        let paths = dg
+            .disjoint_paths("S", "T", DisjointPathsKind::Edge)
+            .unwrap();
+        assert_eq!(paths.len(), 2);
+        for path in &paths {
+            assert_eq!(path.get(0), Some("S"));
+            assert_eq!(path.get(path.len() - 1), Some("T"));
+            assert!(path.iter().any(|node| node == "M"));
+        }
+        assert_eq!(dg.max_flow("S", "T").unwrap(), 2.0);
+    }
+
+    #[test]
+    fn dg_node_disjoint_paths() {
+        let dg = bowtie();
+        let paths = dg
+            .disjoint_paths("S", "T", DisjointPathsKind::Node)
+            .unwrap();
+        assert_eq!(paths.len(), 1);
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["S", "A", "T"]).unwrap();
+        builder.add_path(["S", "B", "T"]).unwrap();
+        builder.add_path(["S", "T"]).unwrap();
+        builder.add_path(["A", "B"]).unwrap();
+        let dg = builder.build_directed();
+        let paths = dg
+            .disjoint_paths("S", "T", DisjointPathsKind::Node)
+            .unwrap();
+        assert_eq!(
+            sorted(paths),
+            vec![vec!["S", "A", "T"], vec!["S", "B", "T"], vec!["S", "T"]]
+        );
+    }
+
+    #[test]
+    fn dg_disjoint_paths_edge_cases() {
+        let dg = bowtie();
+        assert!(dg
+            .disjoint_paths("T", "S", DisjointPathsKind::Edge)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            dg.disjoint_paths("S", "S", DisjointPathsKind::Node)
+                .unwrap(),
+            [vec!["S"]]
+        );
+        assert!(dg
+            .disjoint_paths("S", "Z", DisjointPathsKind::Node)
+            .is_err());
+    }
+
+    #[test]
+    fn dg_disjoint_paths_with_cycles() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["S", "A", "B", "T"]).unwrap();
+        builder.add_path(["S", "B", "A", "T"]).unwrap();
+        let dg = builder.build_directed();
+        for kind in [DisjointPathsKind::Node, DisjointPathsKind::Edge] {
+            let paths = dg.disjoint_paths("S", "T", kind).unwrap();
+            assert_eq!(paths.len(), 2);
+            for path in &paths {
+                let mut nodes = path.as_vec();
+                nodes.sort();
+                nodes.dedup();
+                assert_eq!(nodes.len(), path.len());
+            }
+        }
+    }
+}
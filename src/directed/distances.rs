@@ -0,0 +1,206 @@
+use rayon::prelude::*;
+
+use super::bfs::bfs_distances;
+use crate::{prelude::*, utils::node_map::NodeMap};
+
+impl DirectedGraph {
+    fn distances_from_in(
+        &self,
+        node: impl AsRef<str>,
+        map: &NodeMap,
+    ) -> GraphInteractionResult<Vec<(&str, usize)>> {
+        let node = self.get_internal(node)?;
+        let mut distances = vec![usize::MAX; self.interner.len()];
+        Ok(bfs_distances(map, node, &mut distances, |_, _| {})
+            .into_iter()
+            .map(|node| (self.resolve(node), distances[node.into_usize()]))
+            .collect())
+    }
+
+    fn distance_in(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+        down: &NodeMap,
+        up: &NodeMap,
+    ) -> GraphInteractionResult<Option<usize>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+        let path_buf = unsafe { self.u32x1_vec_0() };
+        let bfs = unsafe { self.bidirectional_bfs_0() };
+        bfs.find_path(down, up, &[from], to, path_buf);
+        Ok(path_buf.len().checked_sub(1))
+    }
+
+    fn distance_matrix_in(
+        &self,
+        nodes: impl IntoIterator<Item = impl AsRef<str>>,
+        map: &NodeMap,
+    ) -> GraphInteractionResult<Vec<Vec<Option<usize>>>> {
+        let mut selected = Vec::new();
+        self.get_internal_mul(nodes, &mut selected)?;
+        let n_symbols = self.interner.len();
+        Ok(selected
+            .par_iter()
+            .map_init(
+                || vec![usize::MAX; n_symbols],
+                |distances, &source| {
+                    let reached = bfs_distances(map, source, distances, |_, _| {});
+                    let row = selected
+                        .iter()
+                        .map(|node| {
+                            let distance = distances[node.into_usize()];
+                            (distance != usize::MAX).then_some(distance)
+                        })
+                        .collect();
+                    for node in reached {
+                        distances[node.into_usize()] = usize::MAX;
+                    }
+                    row
+                },
+            )
+            .collect())
+    }
+
+    /// Returns every node reachable from `node` with the number of
+    /// edges to reach it (its BFS depth), in the order they were
+    /// reached. `node` itself is first with a distance of `0`.
+    pub fn distances_from(
+        &self,
+        node: impl AsRef<str>,
+    ) -> GraphInteractionResult<Vec<(&str, usize)>> {
+        self.distances_from_in(node, &self.children_map)
+    }
+
+    /// Same as [`DirectedGraph::distances_from`] but going up
+    /// through the parents of `node`.
+    pub fn distances_from_upward(
+        &self,
+        node: impl AsRef<str>,
+    ) -> GraphInteractionResult<Vec<(&str, usize)>> {
+        self.distances_from_in(node, &self.parent_map)
+    }
+
+    /// Returns the number of edges in a shortest path from `from`
+    /// to `to`, or `None` if `to` is not reachable.
+    pub fn distance(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<Option<usize>> {
+        self.distance_in(from, to, &self.children_map, &self.parent_map)
+    }
+
+    /// Returns the number of edges in a shortest path going up
+    /// from `from` to its ancestor `to`, or `None` if `to` is not
+    /// an ancestor.
+    pub fn distance_upward(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<Option<usize>> {
+        self.distance_in(from, to, &self.parent_map, &self.children_map)
+    }
+
+    /// Returns the distance between every pair of `nodes`, where
+    /// `matrix[i][j]` is the distance from `nodes[i]` to
+    /// `nodes[j]`. Every row is computed in parallel.
+    pub fn distance_matrix(
+        &self,
+        nodes: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<Vec<Vec<Option<usize>>>> {
+        self.distance_matrix_in(nodes, &self.children_map)
+    }
+
+    /// Same as [`DirectedGraph::distance_matrix`] but going up
+    /// through the parents.
+    pub fn distance_matrix_upward(
+        &self,
+        nodes: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<Vec<Vec<Option<usize>>>> {
+        self.distance_matrix_in(nodes, &self.parent_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn graph() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "D"]).unwrap();
+        builder.add_path(["A", "E", "D"]).unwrap();
+        builder.add_path(["D", "B"]).unwrap();
+        builder.add_path(["X", "Y"]).unwrap();
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_distances_from() {
+        let dg = graph();
+        let mut distances = dg.distances_from("A").unwrap();
+        assert_eq!(distances[0], ("A", 0));
+        distances.sort();
+        assert_eq!(
+            distances,
+            [("A", 0), ("B", 1), ("C", 2), ("D", 2), ("E", 1)]
+        );
+        assert_eq!(dg.distances_from("Y").unwrap(), [("Y", 0)]);
+
+        let mut distances = dg.distances_from_upward("C").unwrap();
+        distances.sort();
+        assert_eq!(
+            distances,
+            [("A", 2), ("B", 1), ("C", 0), ("D", 2), ("E", 3)]
+        );
+        assert!(dg.distances_from("Z").is_err());
+    }
+
+    #[test]
+    fn dg_distance() {
+        let dg = graph();
+        assert_eq!(dg.distance("A", "D").unwrap(), Some(2));
+        assert_eq!(dg.distance("D", "C").unwrap(), Some(2));
+        assert_eq!(dg.distance("A", "A").unwrap(), Some(0));
+        assert_eq!(dg.distance("A", "Y").unwrap(), None);
+        assert_eq!(dg.distance("B", "A").unwrap(), None);
+        assert_eq!(dg.distance_upward("D", "A").unwrap(), Some(2));
+        assert_eq!(dg.distance_upward("A", "D").unwrap(), None);
+        assert!(dg.distance("A", "Z").is_err());
+
+        for (node, distance) in dg.distances_from("A").unwrap() {
+            assert_eq!(dg.distance("A", node).unwrap(), Some(distance));
+            assert_eq!(dg.distance_upward(node, "A").unwrap(), Some(distance));
+        }
+    }
+
+    #[test]
+    fn dg_distance_matrix() {
+        let dg = graph();
+        let nodes = ["A", "C", "D", "Y"];
+        let matrix = dg.distance_matrix(nodes).unwrap();
+        assert_eq!(
+            matrix,
+            [
+                [Some(0), Some(2), Some(2), None],
+                [None, Some(0), Some(1), None],
+                [None, Some(2), Some(0), None],
+                [None, None, None, Some(0)],
+            ]
+        );
+        for (i, from) in nodes.iter().enumerate() {
+            for (j, to) in nodes.iter().enumerate() {
+                assert_eq!(matrix[i][j], dg.distance(from, to).unwrap());
+            }
+        }
+
+        let upward = dg.distance_matrix_upward(nodes).unwrap();
+        for i in 0..nodes.len() {
+            for j in 0..nodes.len() {
+                assert_eq!(upward[i][j], matrix[j][i]);
+            }
+        }
+        assert!(dg.distance_matrix(["A", "Z"]).is_err());
+        assert!(dg.distance_matrix(Vec::<&str>::new()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+use super::acyclic::DirectedAcyclicGraph;
+use crate::{
+    prelude::*,
+    utils::{node_map::NodeMap, sym::Sym},
+};
+
+/// Returns the nodes reachable from `start` following `successors`
+/// in reverse postorder.
+fn reverse_postorder(successors: &NodeMap, start: Sym) -> Vec<Sym> {
+    let mut visited = vec![false; successors.len()];
+    let mut order = Vec::new();
+    // The flag indicates whether the successors of the node have
+    // already been pushed to the stack
+    let mut stack = vec![(start, false)];
+    while let Some((node, expanded)) = stack.pop() {
+        if expanded {
+            order.push(node);
+            continue;
+        }
+        if visited[node.into_usize()] {
+            continue;
+        }
+        visited[node.into_usize()] = true;
+        stack.push((node, true));
+        stack.extend(
+            successors
+                .values(node)
+                .filter(|next| !visited[next.into_usize()])
+                .map(|next| (next, false)),
+        );
+    }
+    order.reverse();
+    order
+}
+
+/// Computes the immediate dominator of every node reachable from
+/// `start` using the iterative algorithm by Cooper, Harvey and
+/// Kennedy. Returns the pairs `(idom, node)` for every node but
+/// `start`.
+pub(crate) fn immediate_dominators(
+    successors: &NodeMap,
+    predecessors: &NodeMap,
+    start: Sym,
+) -> Vec<(Sym, Sym)> {
+    let order = reverse_postorder(successors, start);
+    let mut position = vec![usize::MAX; successors.len()];
+    for (i, node) in order.iter().enumerate() {
+        position[node.into_usize()] = i;
+    }
+
+    // Immediate dominators by position in `order`
+    let mut idoms = vec![usize::MAX; order.len()];
+    idoms[0] = 0;
+
+    let intersect = |idoms: &[usize], mut a: usize, mut b: usize| {
+        while a != b {
+            while a > b {
+                a = idoms[a];
+            }
+            while b > a {
+                b = idoms[b];
+            }
+        }
+        a
+    };
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in 1..order.len() {
+            let new_idom = predecessors
+                .values(order[i])
+                .map(|pred| position[pred.into_usize()])
+                .filter(|&pred| pred != usize::MAX && idoms[pred] != usize::MAX)
+                .reduce(|a, b| intersect(&idoms, a, b));
+            if let Some(new_idom) = new_idom {
+                if idoms[i] != new_idom {
+                    idoms[i] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    (1..order.len())
+        .map(|i| (order[idoms[i]], order[i]))
+        .collect()
+}
+
+impl DirectedGraph {
+    fn dominator_tree_u32(&self, edges: &[(Sym, Sym)]) -> DirectedAcyclicGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        for &(idom, node) in edges {
+            builder.add_edge(self.resolve(idom), self.resolve(node));
+        }
+        // A dominator tree can not have cycles
+        DirectedAcyclicGraph {
+            dg: Box::new(builder.build_directed()),
+        }
+    }
+
+    /// Returns the dominator tree of the nodes reachable from
+    /// `root`. A node dominates another if every path from `root`
+    /// to the other node goes through it, and the parent of each
+    /// node in the tree is its immediate dominator.
+    ///
+    /// NOTE: If `root` has no children the tree is empty.
+    pub fn dominators(
+        &self,
+        root: impl AsRef<str>,
+    ) -> GraphInteractionResult<DirectedAcyclicGraph> {
+        let root = self.get_internal(root)?;
+        let edges = immediate_dominators(&self.children_map, &self.parent_map, root);
+        Ok(self.dominator_tree_u32(&edges))
+    }
+
+    /// Returns the post-dominator tree of the nodes that can reach
+    /// `leaf`. A node post-dominates another if every path from
+    /// the other node to `leaf` goes through it, and the parent of
+    /// each node in the tree is its immediate post-dominator.
+    ///
+    /// NOTE: If `leaf` has no parents the tree is empty.
+    pub fn post_dominators(
+        &self,
+        leaf: impl AsRef<str>,
+    ) -> GraphInteractionResult<DirectedAcyclicGraph> {
+        let leaf = self.get_internal(leaf)?;
+        let edges = immediate_dominators(&self.parent_map, &self.children_map, leaf);
+        Ok(self.dominator_tree_u32(&edges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn parent_of(tree: &DirectedAcyclicGraph, node: &str) -> Vec<String> {
+        tree.parents([node])
+            .unwrap()
+            .iter()
+            .map(|node| node.to_string())
+            .collect()
+    }
+
+    /// Example from "A Simple, Fast Dominance Algorithm"
+    fn control_flow() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["entry", "a", "c", "exit"]).unwrap();
+        builder.add_path(["entry", "b", "d", "exit"]).unwrap();
+        builder.add_path(["a", "d"]).unwrap();
+        builder.add_path(["d", "c"]).unwrap();
+        builder.add_path(["c", "d"]).unwrap();
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_dominators() {
+        let dg = control_flow();
+        let tree = dg.dominators("entry").unwrap();
+        assert_eq!(tree.get_all_roots(), ["entry"]);
+        assert_eq!(parent_of(&tree, "a"), ["entry"]);
+        assert_eq!(parent_of(&tree, "b"), ["entry"]);
+        assert_eq!(parent_of(&tree, "c"), ["entry"]);
+        assert_eq!(parent_of(&tree, "d"), ["entry"]);
+        assert_eq!(parent_of(&tree, "exit"), ["entry"]);
+
+        let tree = dg.dominators("a").unwrap();
+        assert_eq!(parent_of(&tree, "c"), ["a"]);
+        assert_eq!(parent_of(&tree, "exit"), ["a"]);
+        assert!(tree.get_internal("b").is_err());
+    }
+
+    #[test]
+    fn dg_dominators_chain() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "E"]).unwrap();
+        builder.add_path(["B", "D", "E", "F"]).unwrap();
+        let dg = builder.build_directed();
+
+        let tree = dg.dominators("A").unwrap();
+        assert_eq!(parent_of(&tree, "B"), ["A"]);
+        assert_eq!(parent_of(&tree, "E"), ["B"]);
+        assert_eq!(parent_of(&tree, "F"), ["E"]);
+
+        let tree = dg.post_dominators("F").unwrap();
+        assert_eq!(tree.get_all_roots(), ["F"]);
+        assert_eq!(parent_of(&tree, "B"), ["E"]);
+        assert_eq!(parent_of(&tree, "C"), ["E"]);
+        assert_eq!(parent_of(&tree, "A"), ["B"]);
+
+        assert!(dg.dominators("F").unwrap().is_empty());
+        assert!(dg.dominators("Z").is_err());
+    }
+}
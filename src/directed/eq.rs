@@ -0,0 +1,219 @@
+use super::acyclic::DirectedAcyclicGraph;
+use crate::prelude::*;
+
+/// 64-bit FNV-1a. It is defined here, and numbers are written as
+/// little-endian bytes, so the hash does not depend on the platform
+/// or on the version of a dependency.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    fn new() -> Self {
+        Fnv1a(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Writes `name` followed by a delimiter that is never part of
+    /// valid UTF-8.
+    fn write_name(&mut self, name: &str) {
+        self.write(name.as_bytes());
+        self.write(&[0xff]);
+    }
+}
+
+impl DirectedGraph {
+    /// Returns the names of every edge sorted. Parallel edges of
+    /// a multigraph are repeated once per edge.
+    fn sorted_edge_names(&self) -> Vec<(&str, &str)> {
+        let mut edges: Vec<(&str, &str)> = self
+            .edges_u32()
+            .flat_map(|(parent, child)| {
+                std::iter::repeat_n(
+                    (self.resolve(parent), self.resolve(child)),
+                    self.edge_multiplicity_u32(parent, child),
+                )
+            })
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Returns a hash of the nodes and edges of the graph. Two
+    /// graphs that are equal have the same hash regardless of the
+    /// order in which nodes were interned.
+    ///
+    /// The hash is stable between runs and platforms so it can be
+    /// used as a cache key. Every parallel edge of a multigraph is
+    /// part of the hash, while weights, labels and attributes are
+    /// not.
+    pub fn content_hash(&self) -> u64 {
+        let mut nodes: Vec<&str> = self.nodes.iter().map(|&node| self.resolve(node)).collect();
+        nodes.sort_unstable();
+
+        let mut hasher = Fnv1a::new();
+        hasher.write_u64(nodes.len() as u64);
+        for node in nodes {
+            hasher.write_name(node);
+        }
+        let edges = self.sorted_edge_names();
+        hasher.write_u64(edges.len() as u64);
+        for (parent, child) in edges {
+            hasher.write_name(parent);
+            hasher.write_name(child);
+        }
+        hasher.0
+    }
+}
+
+/// Graphs are equal if they have the same nodes and edges by name,
+/// with the same number of parallel edges on multigraphs. Weights,
+/// labels and attributes are not compared.
+impl PartialEq for DirectedGraph {
+    fn eq(&self, other: &Self) -> bool {
+        if self.nodes.len() != other.nodes.len()
+            || self.edges_u32().count() != other.edges_u32().count()
+        {
+            return false;
+        }
+        // Same number of nodes and edges, so if every edge of
+        // `self` is in `other` both graphs are the same
+        self.nodes.iter().all(|&node| {
+            other
+                .interner
+                .get(self.resolve(node))
+                .is_some_and(|sym| other.nodes.binary_search(&sym).is_ok())
+        }) && self.edges_u32().all(|(parent, child)| {
+            other.edge_multiplicity_by_name(self.resolve(parent), self.resolve(child))
+                == self.edge_multiplicity_u32(parent, child)
+        })
+    }
+}
+
+impl Eq for DirectedGraph {}
+
+impl PartialEq for DirectedAcyclicGraph {
+    fn eq(&self, other: &Self) -> bool {
+        self.dg == other.dg
+    }
+}
+
+impl Eq for DirectedAcyclicGraph {}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn dg_eq_ignores_interner_order() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["A", "D"]).unwrap();
+        let first = builder.build_directed();
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "D"]).unwrap();
+        builder.add_path(["B", "C"]).unwrap();
+        builder.add_path(["A", "B"]).unwrap();
+        let second = builder.build_directed();
+
+        assert_eq!(first, second);
+        assert_eq!(first.content_hash(), second.content_hash());
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["D", "A"]).unwrap();
+        let third = builder.build_directed();
+
+        assert_ne!(first, third);
+        assert_ne!(first.content_hash(), third.content_hash());
+    }
+
+    #[test]
+    fn dg_eq_subset() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["X", "B"]).unwrap();
+        let dg = builder.build_directed();
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["B", "C"]).unwrap();
+        let expected = builder.build_directed();
+
+        assert_eq!(dg.subset("B").unwrap(), expected);
+        assert_eq!(
+            dg.subset("B").unwrap().content_hash(),
+            expected.content_hash()
+        );
+        assert_ne!(dg, expected);
+    }
+
+    #[test]
+    fn dg_content_hash_is_stable() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B"]).unwrap();
+        let dg = builder.build_directed();
+        assert_eq!(dg.content_hash(), dg.clone().content_hash());
+        // FNV-1a of `2u64, "A\xff", "B\xff", 1u64, "A\xff", "B\xff"`
+        // with the numbers as little-endian bytes
+        assert_eq!(dg.content_hash(), 0xe47babba6edde5f6);
+
+        // Reference FNV-1a test vector
+        let mut hasher = super::Fnv1a::new();
+        hasher.write(b"a");
+        assert_eq!(hasher.0, 0xaf63dc4c8601ec8c);
+
+        // Names are delimited so moving a character between
+        // nodes changes the hash
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["AB", ""]).unwrap();
+        assert_ne!(dg.content_hash(), builder.build_directed().content_hash());
+    }
+
+    #[test]
+    fn dg_eq_counts_parallel_edges() {
+        let multigraph = |n_parallel: usize| {
+            let mut builder = DirectedGraphBuilder::new();
+            builder.set_multigraph(true);
+            builder.add_edge("B", "C");
+            for _ in 0..n_parallel {
+                builder.add_edge("A", "B");
+            }
+            builder.build_directed()
+        };
+        assert_eq!(multigraph(2), multigraph(2));
+        assert_eq!(multigraph(2).content_hash(), multigraph(2).content_hash());
+        assert_ne!(multigraph(2), multigraph(3));
+        assert_ne!(multigraph(2).content_hash(), multigraph(3).content_hash());
+
+        // A multigraph without parallel edges is the same as a
+        // simple graph
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        let simple = builder.build_directed();
+        assert_eq!(multigraph(1), simple);
+        assert_eq!(multigraph(1).content_hash(), simple.content_hash());
+        assert_ne!(multigraph(2), simple);
+        assert_ne!(simple, multigraph(2));
+    }
+
+    #[test]
+    fn dag_eq() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        let dag = builder.build_acyclic().unwrap();
+        assert_eq!(dag, dag.clone());
+        assert_ne!(dag, dag.clone().reversed());
+    }
+}
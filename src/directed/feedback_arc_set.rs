@@ -0,0 +1,510 @@
+use fxhash::FxHashMap;
+
+use super::acyclic::DirectedAcyclicGraph;
+use crate::{
+    prelude::*,
+    utils::{
+        node_map::{LazySet, NodeMap},
+        sym::Sym,
+    },
+};
+
+/// Largest strongly connected component `feedback_arc_set_exact`
+/// will solve.
+const MAX_EXACT_COMPONENT_SIZE: usize = 20;
+
+/// Edges removed from a graph as `(parent, child)`
+type RemovedEdges = Vec<(Box<str>, Box<str>)>;
+
+impl DirectedGraph {
+    /// Returns the strongly connected components of the graph using
+    /// an iterative version of Tarjan's algorithm.
+    pub(crate) fn strongly_connected_components_u32(&self) -> Vec<Vec<Sym>> {
+        self.strongly_connected_components_within_u32(&self.nodes, |_| true)
+    }
+
+    /// Same as `strongly_connected_components_u32` but only on the
+    /// subgraph made of `nodes`. `in_subgraph` must return `true`
+    /// for exactly the nodes in `nodes`.
+    pub(crate) fn strongly_connected_components_within_u32(
+        &self,
+        nodes: &[Sym],
+        in_subgraph: impl Fn(Sym) -> bool,
+    ) -> Vec<Vec<Sym>> {
+        let subgraph_children = |node: Sym| -> Vec<Sym> {
+            self.children_map
+                .values(node)
+                .filter(|&child| in_subgraph(child))
+                .collect()
+        };
+        let n_symbols = self.interner.len();
+        let mut index = vec![usize::MAX; n_symbols];
+        let mut low = vec![usize::MAX; n_symbols];
+        let mut on_stack = vec![false; n_symbols];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+
+        for &start in nodes {
+            if index[start.into_usize()] != usize::MAX {
+                continue;
+            }
+            // Node and the children that are left to visit
+            let mut call_stack = vec![(start, subgraph_children(start))];
+            index[start.into_usize()] = next_index;
+            low[start.into_usize()] = next_index;
+            next_index += 1;
+            stack.push(start);
+            on_stack[start.into_usize()] = true;
+
+            while let Some((node, children)) = call_stack.last_mut() {
+                let node = *node;
+                if let Some(child) = children.pop() {
+                    if index[child.into_usize()] == usize::MAX {
+                        index[child.into_usize()] = next_index;
+                        low[child.into_usize()] = next_index;
+                        next_index += 1;
+                        stack.push(child);
+                        on_stack[child.into_usize()] = true;
+                        call_stack.push((child, subgraph_children(child)));
+                    } else if on_stack[child.into_usize()] {
+                        low[node.into_usize()] =
+                            low[node.into_usize()].min(index[child.into_usize()]);
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some((parent, _)) = call_stack.last() {
+                    low[parent.into_usize()] = low[parent.into_usize()].min(low[node.into_usize()]);
+                }
+                if low[node.into_usize()] == index[node.into_usize()] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member.into_usize()] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Orders the nodes of a strongly connected component using
+    /// the Eades–Lin–Smyth heuristic so that few edges point
+    /// backwards.
+    ///
+    /// Runs in linear time: sinks and sources are kept in
+    /// worklists, every other node in a bucket by `out - in`
+    /// degree, and removing a node only updates its neighbors.
+    fn eades_lin_smyth_order_u32(&self, component: &[Sym]) -> Vec<Sym> {
+        let n = component.len();
+        let position: FxHashMap<Sym, usize> = component
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| (node, i))
+            .collect();
+        let local = |neighbors: &NodeMap, node: Sym| -> Vec<usize> {
+            neighbors
+                .values(node)
+                .filter(|&neighbor| neighbor != node)
+                .filter_map(|neighbor| position.get(&neighbor).copied())
+                .collect()
+        };
+        let children: Vec<Vec<usize>> = component
+            .iter()
+            .map(|&node| local(&self.children_map, node))
+            .collect();
+        let parents: Vec<Vec<usize>> = component
+            .iter()
+            .map(|&node| local(&self.parent_map, node))
+            .collect();
+        let mut out_degree: Vec<usize> = children.iter().map(Vec::len).collect();
+        let mut in_degree: Vec<usize> = parents.iter().map(Vec::len).collect();
+
+        // `out - in` ranges over `-(n - 1)..=(n - 1)`, shifted so
+        // it can index the buckets. Entries are not removed when a
+        // node moves, stale ones are skipped when popped.
+        let bucket = |i: usize, out_degree: &[usize], in_degree: &[usize]| {
+            n - 1 + out_degree[i] - in_degree[i]
+        };
+        let mut buckets = vec![Vec::new(); 2 * n - 1];
+        let mut max_bucket = 0;
+        let mut sinks = Vec::new();
+        let mut sources = Vec::new();
+        for i in (0..n).rev() {
+            let b = bucket(i, &out_degree, &in_degree);
+            buckets[b].push(i);
+            max_bucket = max_bucket.max(b);
+            if out_degree[i] == 0 {
+                sinks.push(i);
+            } else if in_degree[i] == 0 {
+                sources.push(i);
+            }
+        }
+
+        let mut removed = vec![false; n];
+        let mut head = Vec::with_capacity(n);
+        let mut tail = Vec::new();
+        let mut n_removed = 0;
+        while n_removed < n {
+            // Sinks go to the end and sources to the start
+            let next = if let Some(sink) = sinks.pop() {
+                if removed[sink] {
+                    continue;
+                }
+                tail.push(sink);
+                sink
+            } else if let Some(source) = sources.pop() {
+                if removed[source] {
+                    continue;
+                }
+                head.push(source);
+                source
+            } else {
+                let Some(best) = buckets[max_bucket].pop() else {
+                    max_bucket -= 1;
+                    continue;
+                };
+                if removed[best] || bucket(best, &out_degree, &in_degree) != max_bucket {
+                    continue;
+                }
+                head.push(best);
+                best
+            };
+
+            removed[next] = true;
+            n_removed += 1;
+            for &child in &children[next] {
+                if !removed[child] {
+                    in_degree[child] -= 1;
+                    if in_degree[child] == 0 {
+                        sources.push(child);
+                    }
+                    let b = bucket(child, &out_degree, &in_degree);
+                    buckets[b].push(child);
+                    max_bucket = max_bucket.max(b);
+                }
+            }
+            for &parent in &parents[next] {
+                if !removed[parent] {
+                    out_degree[parent] -= 1;
+                    if out_degree[parent] == 0 {
+                        sinks.push(parent);
+                    }
+                    buckets[bucket(parent, &out_degree, &in_degree)].push(parent);
+                }
+            }
+        }
+
+        tail.reverse();
+        head.extend(tail);
+        head.into_iter().map(|i| component[i]).collect()
+    }
+
+    /// Finds the order of a strongly connected component with the
+    /// fewest edges pointing backwards by trying every subset of
+    /// its nodes.
+    fn exact_order_u32(&self, component: &[Sym]) -> Vec<Sym> {
+        let n = component.len();
+        let position = |node: Sym| component.iter().position(|&member| member == node);
+        // Bit mask of the children of every node in the component
+        let children_masks: Vec<u32> = component
+            .iter()
+            .map(|&node| {
+                self.children_map
+                    .values(node)
+                    .filter_map(position)
+                    .fold(0, |mask, i| mask | (1 << i))
+            })
+            .collect();
+
+        // `cost[mask]` is the fewest backwards edges when the nodes
+        // in `mask` are placed first
+        let mut cost = vec![usize::MAX; 1 << n];
+        let mut last = vec![0; 1 << n];
+        cost[0] = 0;
+        for mask in 0..(1usize << n) {
+            if cost[mask] == usize::MAX {
+                continue;
+            }
+            for (i, &children) in children_masks.iter().enumerate() {
+                if mask & (1 << i) != 0 {
+                    continue;
+                }
+                // Edges from `i` to nodes already placed point
+                // backwards
+                let next_cost = cost[mask] + (children as usize & mask).count_ones() as usize;
+                let next = mask | (1 << i);
+                if next_cost < cost[next] {
+                    cost[next] = next_cost;
+                    last[next] = i;
+                }
+            }
+        }
+
+        let mut order = Vec::with_capacity(n);
+        let mut mask = (1usize << n) - 1;
+        while mask != 0 {
+            order.push(component[last[mask]]);
+            mask &= !(1 << last[mask]);
+        }
+        order.reverse();
+        order
+    }
+
+    /// Returns the edges pointing backwards when every component
+    /// is ordered with `order`, including self loops.
+    fn feedback_arc_set_u32(&self, order: impl Fn(&[Sym]) -> Vec<Sym>) -> Vec<(Sym, Sym)> {
+        let components = self.strongly_connected_components_u32();
+        let mut component_of = vec![usize::MAX; self.interner.len()];
+        for (i, component) in components.iter().enumerate() {
+            for node in component {
+                component_of[node.into_usize()] = i;
+            }
+        }
+
+        let mut position = vec![0; self.interner.len()];
+        let mut feedback_edges = Vec::new();
+        for (i, component) in components.iter().enumerate() {
+            if component.len() == 1 {
+                let node = component[0];
+                if self.has_edge_u32(node, node) {
+                    feedback_edges.push((node, node));
+                }
+                continue;
+            }
+            for (position_in_order, node) in order(component).into_iter().enumerate() {
+                position[node.into_usize()] = position_in_order;
+            }
+            for &node in component {
+                for child in self.children_map.values(node) {
+                    if component_of[child.into_usize()] == i
+                        && position[child.into_usize()] <= position[node.into_usize()]
+                    {
+                        feedback_edges.push((node, child));
+                    }
+                }
+            }
+        }
+        feedback_edges
+    }
+
+    fn resolve_edges(&self, edges: &[(Sym, Sym)]) -> Vec<(&str, &str)> {
+        edges
+            .iter()
+            .map(|&(parent, child)| (self.resolve(parent), self.resolve(child)))
+            .collect()
+    }
+
+    /// Returns a set of edges whose removal makes the graph
+    /// acyclic using the Eades–Lin–Smyth heuristic. The result is
+    /// small but not guaranteed to be the smallest.
+    ///
+    /// Edges are returned as `(parent, child)` and the set is
+    /// empty if the graph is already acyclic.
+    pub fn feedback_arc_set(&self) -> Vec<(&str, &str)> {
+        let edges =
+            self.feedback_arc_set_u32(|component| self.eades_lin_smyth_order_u32(component));
+        self.resolve_edges(&edges)
+    }
+
+    /// Returns the smallest set of edges whose removal makes the
+    /// graph acyclic.
+    ///
+    /// The running time grows exponentially with the size of the
+    /// largest cycle, so `None` is returned if any strongly
+    /// connected component has more than 20 nodes.
+    pub fn feedback_arc_set_exact(&self) -> Option<Vec<(&str, &str)>> {
+        let too_large = self
+            .strongly_connected_components_u32()
+            .iter()
+            .any(|component| component.len() > MAX_EXACT_COMPONENT_SIZE);
+        if too_large {
+            return None;
+        }
+        let edges = self.feedback_arc_set_u32(|component| self.exact_order_u32(component));
+        Some(self.resolve_edges(&edges))
+    }
+
+    /// Removes `feedback_edges` from the graph and builds a
+    /// [`DirectedAcyclicGraph`]. Also returns the edges that were
+    /// actually removed, edges that are not in the graph are
+    /// ignored.
+    ///
+    /// Returns an error if the graph still has a cycle.
+    pub fn into_acyclic_by_removing(
+        mut self,
+        feedback_edges: impl IntoIterator<Item = (impl AsRef<str>, impl AsRef<str>)>,
+    ) -> Result<(DirectedAcyclicGraph, RemovedEdges), GraphHasCycle> {
+        let mut removed = Vec::new();
+        for (parent, child) in feedback_edges {
+            let (parent, child) = (parent.as_ref(), child.as_ref());
+            if !self.has_edge_by_name(parent, child) {
+                continue;
+            }
+            let (Ok(parent_sym), Ok(child_sym)) =
+                (self.get_internal(parent), self.get_internal(child))
+            else {
+                continue;
+            };
+            self.remove_edge_u32(parent_sym, child_sym);
+            removed.push((parent.into(), child.into()));
+        }
+
+        if !removed.is_empty() {
+            self.leaves.sort_unstable();
+            self.roots.sort_unstable();
+            let children_map = &self.children_map;
+            self.weights = self
+                .weights
+                .take()
+                .map(|weights| weights.retain_edges(children_map));
+            self.labels = self
+                .labels
+                .take()
+                .map(|labels| labels.retain_edges(children_map));
+            self.edge_ids = self
+                .edge_ids
+                .take()
+                .map(|edge_ids| edge_ids.retain_edges(children_map));
+            self.parallel_weights = self
+                .parallel_weights
+                .take()
+                .map(|weights| weights.retain_edges(children_map));
+        }
+
+        Ok((DirectedAcyclicGraph::build(self)?, removed))
+    }
+
+    /// Removes the edge `parent -> child` updating the roots and
+    /// leaves. Assumes the edge exists.
+    fn remove_edge_u32(&mut self, parent: Sym, child: Sym) {
+        self.n_edges -= self.edge_multiplicity_u32(parent, child);
+        if let LazySet::Initialized(children) = self.children_map.get_mut(parent) {
+            children.remove(&child);
+            if children.is_empty() {
+                self.children_map.get_mut(parent).into_empty();
+                self.leaves.push(parent);
+            }
+        }
+        if let LazySet::Initialized(parents) = self.parent_map.get_mut(child) {
+            parents.remove(&parent);
+            if parents.is_empty() {
+                self.parent_map.get_mut(child).into_empty();
+                self.roots.push(child);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn cyclic() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "A"]).unwrap();
+        builder.add_path(["C", "D", "E", "C"]).unwrap();
+        builder.add_path(["E", "F"]).unwrap();
+        builder.add_path(["F", "F"]).unwrap();
+        builder.add_path(["X", "A"]).unwrap();
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_strongly_connected_components() {
+        let dg = cyclic();
+        let mut components: Vec<Vec<&str>> = dg
+            .strongly_connected_components_u32()
+            .into_iter()
+            .map(|component| {
+                let mut names: Vec<&str> = component.iter().map(|&n| dg.resolve(n)).collect();
+                names.sort();
+                names
+            })
+            .collect();
+        components.sort();
+        assert_eq!(
+            components,
+            vec![vec!["A", "B", "C", "D", "E"], vec!["F"], vec!["X"]]
+        );
+    }
+
+    #[test]
+    fn dg_feedback_arc_set() {
+        let dg = cyclic();
+        let feedback_edges = dg.feedback_arc_set();
+        // One edge for each cycle and the self loop
+        assert_eq!(feedback_edges.len(), 3);
+        assert!(feedback_edges.contains(&("F", "F")));
+
+        let (dag, removed) = dg.clone().into_acyclic_by_removing(feedback_edges).unwrap();
+        assert_eq!(removed.len(), 3);
+        assert_eq!(dag.n_edges(), dg.n_edges() - 3);
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        assert!(builder.build_directed().feedback_arc_set().is_empty());
+    }
+
+    #[test]
+    fn dg_feedback_arc_set_exact() {
+        // A tournament where the heuristic can not do better than
+        // the exact solution
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "D", "A"]).unwrap();
+        builder.add_path(["A", "C"]).unwrap();
+        builder.add_path(["B", "D"]).unwrap();
+        builder.add_path(["D", "B"]).unwrap();
+        let dg = builder.build_directed();
+
+        let exact = dg.feedback_arc_set_exact().unwrap();
+        assert_eq!(exact.len(), 2);
+        assert!(dg.feedback_arc_set().len() >= exact.len());
+        assert!(dg.clone().into_acyclic_by_removing(exact).is_ok());
+
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..30 {
+            builder.add_edge(i.to_string(), ((i + 1) % 30).to_string());
+        }
+        let dg = builder.build_directed();
+        assert!(dg.feedback_arc_set_exact().is_none());
+        assert_eq!(dg.feedback_arc_set().len(), 1);
+    }
+
+    #[test]
+    fn dg_into_acyclic_by_removing() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 2.0);
+        builder.add_weighted_edge("B", "A", 3.0);
+        builder.add_edge("B", "C");
+        let dg = builder.build_directed();
+
+        assert!(dg.clone().into_acyclic_by_removing([("A", "C")]).is_err());
+
+        let (dag, removed) = dg
+            .into_acyclic_by_removing([("B", "A"), ("A", "C"), ("Z", "A")])
+            .unwrap();
+        assert_eq!(removed, [("B".into(), "A".into())]);
+        assert_eq!(dag.get_all_roots(), ["A"]);
+        assert_eq!(dag.edge_weight("A", "B").unwrap(), Some(2.0));
+        assert_eq!(dag.edge_weight("B", "A").unwrap(), None);
+        assert_eq!(dag.n_edges(), 2);
+
+        // Removing an edge can turn a node into a leaf
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "A"]).unwrap();
+        let (dag, _) = builder
+            .build_directed()
+            .into_acyclic_by_removing([("B", "A")])
+            .unwrap();
+        assert_eq!(dag.get_all_leaves(), ["B"]);
+        assert_eq!(dag.get_all_roots(), ["A"]);
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::VecDeque;
+
+use crate::{prelude::*, utils::sym::Sym};
+
+/// Residual capacities below this value are treated as zero
+const EPSILON: f64 = 1e-12;
+
+/// Residual network used by Dinic's algorithm. Edges are stored
+/// in pairs so the reverse of edge `e` is `e ^ 1`.
+pub(crate) struct FlowNetwork {
+    pub(crate) adjacency: Vec<Vec<usize>>,
+    pub(crate) to: Vec<Sym>,
+    pub(crate) capacity: Vec<f64>,
+}
+
+impl FlowNetwork {
+    pub(crate) fn new(n_nodes: usize) -> Self {
+        FlowNetwork {
+            adjacency: vec![Vec::new(); n_nodes],
+            to: Vec::new(),
+            capacity: Vec::new(),
+        }
+    }
+
+    pub(crate) fn add_edge(&mut self, from: Sym, to: Sym, capacity: f64) {
+        self.adjacency[from.into_usize()].push(self.to.len());
+        self.to.push(to);
+        self.capacity.push(capacity);
+        self.adjacency[to.into_usize()].push(self.to.len());
+        self.to.push(from);
+        self.capacity.push(0.0);
+    }
+
+    /// Returns the BFS level of every node in the residual
+    /// network. Unreachable nodes have a level of `usize::MAX`.
+    fn levels(&self, source: Sym) -> Vec<usize> {
+        let mut levels = vec![usize::MAX; self.adjacency.len()];
+        levels[source.into_usize()] = 0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &edge in &self.adjacency[node.into_usize()] {
+                let next = self.to[edge];
+                if self.capacity[edge] > EPSILON && levels[next.into_usize()] == usize::MAX {
+                    levels[next.into_usize()] = levels[node.into_usize()] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        levels
+    }
+
+    /// Returns the flow going through edge `edge`, which must be
+    /// one of the edges added with `add_edge`.
+    #[inline]
+    pub(crate) fn flow(&self, edge: usize) -> f64 {
+        self.capacity[edge ^ 1]
+    }
+
+    /// Runs Dinic's algorithm and returns the value of the flow.
+    pub(crate) fn max_flow(&mut self, source: Sym, sink: Sym) -> f64 {
+        let mut flow = 0.0;
+        if source == sink {
+            return flow;
+        }
+        loop {
+            let mut levels = self.levels(source);
+            if levels[sink.into_usize()] == usize::MAX {
+                return flow;
+            }
+            flow += self.blocking_flow(source, sink, &mut levels);
+        }
+    }
+
+    /// Pushes a blocking flow through the level graph. Returns the
+    /// amount of flow pushed.
+    fn blocking_flow(&mut self, source: Sym, sink: Sym, levels: &mut [usize]) -> f64 {
+        let mut next_edge = vec![0; self.adjacency.len()];
+        let mut path = Vec::new();
+        let mut node = source;
+        let mut total = 0.0;
+        loop {
+            if node == sink {
+                let bottleneck = path
+                    .iter()
+                    .map(|&edge| self.capacity[edge])
+                    .fold(f64::INFINITY, f64::min);
+                for &edge in &path {
+                    self.capacity[edge] -= bottleneck;
+                    self.capacity[edge ^ 1] += bottleneck;
+                }
+                total += bottleneck;
+                path.clear();
+                node = source;
+                continue;
+            }
+
+            let edges = &self.adjacency[node.into_usize()];
+            let advance = edges[next_edge[node.into_usize()]..]
+                .iter()
+                .position(|&edge| {
+                    let next = self.to[edge];
+                    self.capacity[edge] > EPSILON
+                        && levels[next.into_usize()] == levels[node.into_usize()].wrapping_add(1)
+                });
+            match advance {
+                Some(offset) => {
+                    next_edge[node.into_usize()] += offset;
+                    let edge = edges[next_edge[node.into_usize()]];
+                    path.push(edge);
+                    node = self.to[edge];
+                }
+                None => {
+                    // Dead end, nothing else can go through this node
+                    next_edge[node.into_usize()] = edges.len();
+                    levels[node.into_usize()] = usize::MAX;
+                    match path.pop() {
+                        Some(edge) => node = self.to[edge ^ 1],
+                        None => return total,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl DirectedGraph {
+    /// Builds the residual network using the edge weights as
+    /// capacities.
+    fn flow_network_u32(&self) -> GraphInteractionResult<FlowNetwork> {
+        let mut network = FlowNetwork::new(self.interner.len());
+        for (parent, child) in self.edges_u32() {
+            let capacity = self.weight_u32(parent, child);
+            if capacity.is_nan() {
+                return Err(GraphInteractionError::NaNWeight(
+                    self.resolve(parent).into(),
+                    self.resolve(child).into(),
+                ));
+            }
+            if capacity < 0.0 {
+                return Err(GraphInteractionError::NegativeWeight(
+                    self.resolve(parent).into(),
+                    self.resolve(child).into(),
+                ));
+            }
+            network.add_edge(parent, child, self.total_weight_u32(parent, child));
+        }
+        Ok(network)
+    }
+
+    /// Runs Dinic's algorithm and returns the value of the flow
+    /// along with the residual network.
+    fn max_flow_u32(&self, source: Sym, sink: Sym) -> GraphInteractionResult<(f64, FlowNetwork)> {
+        let mut network = self.flow_network_u32()?;
+        let flow = network.max_flow(source, sink);
+        Ok((flow, network))
+    }
+
+    /// Returns the maximum flow that can be sent from `source` to
+    /// `sink` using the edge weights as capacities.
+    ///
+    /// Unweighted edges have a capacity of `1.0`, so on an
+    /// unweighted graph this is the number of edge-disjoint paths.
+    /// Parallel edges of a multigraph add up their capacities.
+    ///
+    /// Returns an error if an edge has a negative or NaN weight.
+    pub fn max_flow(
+        &self,
+        source: impl AsRef<str>,
+        sink: impl AsRef<str>,
+    ) -> GraphInteractionResult<f64> {
+        let source = self.get_internal(source)?;
+        let sink = self.get_internal(sink)?;
+        let (flow, _) = self.max_flow_u32(source, sink)?;
+        Ok(flow)
+    }
+
+    /// Returns the edges of a minimum cut between `source` and
+    /// `sink` as `(parent, child)` along with its capacity, which
+    /// is equal to the maximum flow.
+    ///
+    /// Removing the returned edges leaves `sink` unreachable from
+    /// `source`.
+    pub fn min_cut(
+        &self,
+        source: impl AsRef<str>,
+        sink: impl AsRef<str>,
+    ) -> GraphInteractionResult<(Vec<(&str, &str)>, f64)> {
+        let source = self.get_internal(source)?;
+        let sink = self.get_internal(sink)?;
+        let (flow, network) = self.max_flow_u32(source, sink)?;
+        if source == sink {
+            return Ok((Vec::new(), flow));
+        }
+
+        // Nodes still reachable from the source in the residual
+        // network are on the source side of the cut
+        let levels = network.levels(source);
+        let reachable = |node: Sym| levels[node.into_usize()] != usize::MAX;
+        let cut = self
+            .edges_u32()
+            .filter(|&(parent, child)| reachable(parent) && !reachable(child))
+            .map(|(parent, child)| (self.resolve(parent), self.resolve(child)))
+            .collect();
+        Ok((cut, flow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    /// Example from the Wikipedia article on the max-flow min-cut
+    /// theorem
+    fn network() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("s", "o", 3.0);
+        builder.add_weighted_edge("s", "p", 3.0);
+        builder.add_weighted_edge("o", "p", 2.0);
+        builder.add_weighted_edge("o", "q", 3.0);
+        builder.add_weighted_edge("p", "r", 2.0);
+        builder.add_weighted_edge("q", "r", 4.0);
+        builder.add_weighted_edge("q", "t", 2.0);
+        builder.add_weighted_edge("r", "t", 3.0);
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_max_flow() {
+        let dg = network();
+        assert_eq!(dg.max_flow("s", "t").unwrap(), 5.0);
+        assert_eq!(dg.max_flow("o", "t").unwrap(), 5.0);
+        assert_eq!(dg.max_flow("t", "s").unwrap(), 0.0);
+        assert_eq!(dg.max_flow("s", "s").unwrap(), 0.0);
+        assert!(dg.max_flow("s", "z").is_err());
+    }
+
+    #[test]
+    fn dg_min_cut() {
+        let dg = network();
+        let (cut, capacity) = dg.min_cut("s", "t").unwrap();
+        assert_eq!(capacity, 5.0);
+        let cut_capacity: f64 = cut
+            .iter()
+            .map(|&(parent, child)| dg.edge_weight(parent, child).unwrap().unwrap())
+            .sum();
+        assert_eq!(cut_capacity, 5.0);
+
+        // Removing the cut disconnects the sink
+        let (dag, removed) = dg.clone().into_acyclic_by_removing(cut).unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(dag.find_path("s", "t").unwrap().is_empty());
+
+        let (cut, capacity) = dg.min_cut("t", "s").unwrap();
+        assert!(cut.is_empty());
+        assert_eq!(capacity, 0.0);
+    }
+
+    #[test]
+    fn dg_max_flow_unit_capacities() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "D"]).unwrap();
+        builder.add_path(["A", "C", "D"]).unwrap();
+        builder.add_path(["A", "D"]).unwrap();
+        builder.add_path(["B", "C"]).unwrap();
+        let dg = builder.build_directed();
+        // Three edge-disjoint paths from A to D
+        assert_eq!(dg.max_flow("A", "D").unwrap(), 3.0);
+        assert_eq!(dg.max_flow("B", "D").unwrap(), 2.0);
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.set_multigraph(true);
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["A", "B"]).unwrap();
+        let dg = builder.build_directed();
+        assert_eq!(dg.max_flow("A", "B").unwrap(), 2.0);
+        assert_eq!(dg.max_flow("A", "C").unwrap(), 1.0);
+    }
+
+    #[test]
+    fn dg_max_flow_negative_capacity() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", -1.0);
+        let dg = builder.build_directed();
+        assert!(matches!(
+            dg.max_flow("A", "B"),
+            Err(GraphInteractionError::NegativeWeight(_, _))
+        ));
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", f64::NAN);
+        let dg = builder.build_directed();
+        assert!(matches!(
+            dg.max_flow("A", "B"),
+            Err(GraphInteractionError::NaNWeight(_, _))
+        ));
+    }
+
+    #[test]
+    fn dg_max_flow_long_chain() {
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..100_000 {
+            builder.add_edge(i.to_string(), (i + 1).to_string());
+        }
+        let dg = builder.build_directed();
+        assert_eq!(dg.max_flow("0", "100000").unwrap(), 1.0);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    prelude::*,
+    utils::{node_map::LazySet, node_set::NodeVec, sym::Sym},
+};
+
+impl DirectedGraph {
+    #[inline]
+    pub(crate) fn get_label_internal(&self, label: impl AsRef<str>) -> GraphInteractionResult<Sym> {
+        self.labels
+            .as_ref()
+            .and_then(|labels| labels.interner.get(label.as_ref()))
+            .ok_or_else(|| GraphInteractionError::LabelNotExist(label.as_ref().into()))
+    }
+
+    fn get_label_internal_mul(
+        &self,
+        labels: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<Vec<Sym>> {
+        labels
+            .into_iter()
+            .map(|label| self.get_label_internal(label))
+            .collect()
+    }
+
+    /// Returns every label used on the edges of the graph.
+    pub fn edge_label_names(&self) -> Vec<&str> {
+        match &self.labels {
+            Some(labels) => (0..labels.interner.len())
+                .map(|i| unsafe { labels.interner.resolve_unchecked(Sym::new(i as u32)) })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the labels of the edge `from -> to`. The result is
+    /// empty if the edge does not exist or has no labels.
+    pub fn edge_labels(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<Vec<&str>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+        Ok(match &self.labels {
+            Some(labels) => labels
+                .get(from, to)
+                .iter()
+                .map(|&label| unsafe { labels.interner.resolve_unchecked(label) })
+                .collect(),
+            None => Vec::new(),
+        })
+    }
+
+    /// This function returns the children of a given set of
+    /// nodes that are connected through an edge with `label`.
+    ///
+    /// NOTE: The returned `Vec` may include duplicates.
+    pub fn children_by_label(
+        &self,
+        nodes: impl IntoIterator<Item = impl AsRef<str>>,
+        label: impl AsRef<str>,
+    ) -> GraphInteractionResult<NodeVec> {
+        let label = self.get_label_internal(label)?;
+        let nodes_buf = unsafe { self.u32x1_vec_0() };
+        let res = unsafe { self.u32x1_vec_1() };
+        self.get_internal_mul(nodes, nodes_buf)?;
+        if let Some(labels) = &self.labels {
+            for &parent in nodes_buf.iter() {
+                if let LazySet::Initialized(children) = self.children_map.get(parent) {
+                    res.extend(
+                        children
+                            .iter()
+                            .filter(|&&child| labels.has_any_label(parent, child, &[label])),
+                    );
+                }
+            }
+        }
+        Ok(self.resolve_mul_slice(res))
+    }
+
+    /// This function returns the parents of a given set of
+    /// nodes that are connected through an edge with `label`.
+    ///
+    /// NOTE: The returned `Vec` may include duplicates.
+    pub fn parents_by_label(
+        &self,
+        nodes: impl IntoIterator<Item = impl AsRef<str>>,
+        label: impl AsRef<str>,
+    ) -> GraphInteractionResult<NodeVec> {
+        let label = self.get_label_internal(label)?;
+        let nodes_buf = unsafe { self.u32x1_vec_0() };
+        let res = unsafe { self.u32x1_vec_1() };
+        self.get_internal_mul(nodes, nodes_buf)?;
+        if let Some(labels) = &self.labels {
+            for &child in nodes_buf.iter() {
+                if let LazySet::Initialized(parents) = self.parent_map.get(child) {
+                    res.extend(
+                        parents
+                            .iter()
+                            .filter(|&&parent| labels.has_any_label(parent, child, &[label])),
+                    );
+                }
+            }
+        }
+        Ok(self.resolve_mul_slice(res))
+    }
+
+    /// Returns a new tree that is the subset of all children under a
+    /// node, only following the edges with any of `labels`.
+    pub fn subset_by_labels(
+        &self,
+        node: impl AsRef<str>,
+        labels: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<DirectedGraph> {
+        self.subset_multi_by_labels([node], labels)
+    }
+
+    /// Returns a new tree that is the subset of all children under
+    /// some nodes, only following the edges with any of `labels`.
+    pub fn subset_multi_by_labels(
+        &self,
+        nodes: impl IntoIterator<Item = impl AsRef<str>>,
+        labels: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<DirectedGraph> {
+        let selected_labels = self.get_label_internal_mul(labels)?;
+        let buf = unsafe { self.u32x1_vec_0() };
+        self.get_internal_mul(nodes, buf)?;
+        Ok(self.subset_multi_u32_filtered(buf, |parent, child| {
+            self.labels
+                .as_ref()
+                .is_some_and(|labels| labels.has_any_label(parent, child, &selected_labels))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn ontology() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_labeled_edge("animal", "dog", "is_a");
+        builder.add_labeled_edge("animal", "cat", "is_a");
+        builder.add_labeled_edge("dog", "tail", "part_of");
+        builder.add_labeled_edge("dog", "puppy", "is_a");
+        builder.add_labeled_edge("dog", "puppy", "depends_on");
+        builder.add_labeled_edge("cat", "tail", "part_of");
+        builder.add_edge("cat", "kitten");
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_edge_labels() {
+        let dg = ontology();
+        assert_eq!(dg.edge_label_names(), ["is_a", "part_of", "depends_on"]);
+        assert_eq!(
+            dg.edge_labels("dog", "puppy").unwrap(),
+            ["is_a", "depends_on"]
+        );
+        assert_eq!(dg.edge_labels("animal", "dog").unwrap(), ["is_a"]);
+        assert!(dg.edge_labels("cat", "kitten").unwrap().is_empty());
+        assert!(dg.edge_labels("puppy", "dog").unwrap().is_empty());
+        assert!(dg.edge_labels("dog", "unicorn").is_err());
+    }
+
+    #[test]
+    fn dg_children_and_parents_by_label() {
+        let dg = ontology();
+        let children = dg.children_by_label(["dog"], "is_a").unwrap();
+        assert_eq!(children, ["puppy"]);
+
+        let children = dg.children_by_label(["dog", "cat"], "part_of").unwrap();
+        assert_eq!(children, ["tail", "tail"]);
+
+        let parents = dg.parents_by_label(["tail", "puppy"], "part_of").unwrap();
+        let mut parents = parents.as_vec();
+        parents.sort();
+        assert_eq!(parents, ["cat", "dog"]);
+
+        assert!(matches!(
+            dg.children_by_label(["dog"], "eats"),
+            Err(GraphInteractionError::LabelNotExist(_))
+        ));
+    }
+
+    #[test]
+    fn dg_subset_by_labels() {
+        let dg = ontology();
+        let dg2 = dg.subset_by_labels("animal", ["is_a"]).unwrap();
+        let nodes = dg2.nodes();
+        let mut nodes = nodes.as_vec();
+        nodes.sort();
+        assert_eq!(nodes, ["animal", "cat", "dog", "puppy"]);
+        let leaves = dg2.get_all_leaves();
+        let mut leaves = leaves.as_vec();
+        leaves.sort();
+        assert_eq!(leaves, ["cat", "puppy"]);
+        assert_eq!(
+            dg2.edge_labels("dog", "puppy").unwrap(),
+            ["is_a", "depends_on"]
+        );
+
+        let dg3 = dg
+            .subset_multi_by_labels(["dog", "cat"], ["part_of", "depends_on"])
+            .unwrap();
+        let nodes = dg3.nodes();
+        let mut nodes = nodes.as_vec();
+        nodes.sort();
+        assert_eq!(nodes, ["cat", "dog", "puppy", "tail"]);
+
+        let dg4 = dg.subset_by_labels("animal", ["part_of"]).unwrap();
+        assert_eq!(dg4.nodes(), ["animal"]);
+    }
+}
@@ -0,0 +1,248 @@
+use rayon::prelude::*;
+
+use super::bfs::bfs_distances;
+use crate::{
+    prelude::*,
+    utils::{node_map::NodeMap, node_set::NodeVec, sym::Sym},
+};
+
+impl DirectedGraph {
+    /// Finds a shortest path from every node in `from` to every
+    /// node in `to`. The result is a matrix where `paths[i][j]` is
+    /// the path from `from[i]` to `to[j]`, empty if there is none.
+    ///
+    /// A single BFS is run from every node in `from`, or up from
+    /// every node in `to` when there are fewer of them, and those
+    /// are run in parallel.
+    pub fn find_paths_many_to_many(
+        &self,
+        from: impl IntoIterator<Item = impl AsRef<str>>,
+        to: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> GraphInteractionResult<Vec<Vec<NodeVec>>> {
+        let mut sources = Vec::new();
+        let mut targets = Vec::new();
+        self.get_internal_mul(from, &mut sources)?;
+        self.get_internal_mul(to, &mut targets)?;
+
+        if targets.len() >= sources.len() {
+            return Ok(self
+                .paths_from_each(&self.children_map, &sources, &targets)
+                .into_iter()
+                .map(|row| {
+                    row.into_iter()
+                        .map(|mut path| {
+                            path.reverse();
+                            self.resolve_mul_slice(&path)
+                        })
+                        .collect()
+                })
+                .collect());
+        }
+
+        // Going up from the targets gives one column at a time
+        // with every path already running from source to target.
+        let columns = self.paths_from_each(&self.parent_map, &targets, &sources);
+        Ok((0..sources.len())
+            .map(|i| {
+                columns
+                    .iter()
+                    .map(|column| self.resolve_mul_slice(&column[i]))
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Runs a BFS following `map` from every node in `starts` and
+    /// returns, for each of them, a shortest path from every node
+    /// in `ends` back to it. Paths are empty when not reachable.
+    fn paths_from_each(&self, map: &NodeMap, starts: &[Sym], ends: &[Sym]) -> Vec<Vec<Vec<Sym>>> {
+        let n_symbols = self.interner.len();
+        starts
+            .par_iter()
+            .map_init(
+                || (vec![usize::MAX; n_symbols], vec![None; n_symbols]),
+                |(distances, previous), &start| {
+                    let reached = bfs_distances(map, start, distances, |node, next| {
+                        previous[next.into_usize()].get_or_insert(node);
+                    });
+                    let paths = ends
+                        .iter()
+                        .map(|&end| {
+                            let mut path = Vec::new();
+                            if distances[end.into_usize()] != usize::MAX {
+                                let mut current = end;
+                                path.push(current);
+                                while let Some(node) = previous[current.into_usize()] {
+                                    current = node;
+                                    path.push(current);
+                                }
+                            }
+                            path
+                        })
+                        .collect();
+                    for node in reached {
+                        distances[node.into_usize()] = usize::MAX;
+                        previous[node.into_usize()] = None;
+                    }
+                    paths
+                },
+            )
+            .collect()
+    }
+
+    /// Finds a shortest path to `to` from whichever node in `from`
+    /// is the closest. The path starts on that node and is empty
+    /// if `to` is not reachable from any of them.
+    pub fn multi_source_shortest_path(
+        &self,
+        from: impl IntoIterator<Item = impl AsRef<str>>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<NodeVec> {
+        let sources = unsafe { self.u32x1_vec_1() };
+        self.get_internal_mul(from, sources)?;
+        let to = self.get_internal(to)?;
+        let path_buf = unsafe { self.u32x1_vec_0() };
+        let bfs = unsafe { self.bidirectional_bfs_0() };
+        bfs.find_path(&self.children_map, &self.parent_map, sources, to, path_buf);
+        Ok(self.resolve_mul_slice(path_buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    /// Checks that `path` is a shortest path from `from` to `to`
+    /// without requiring it to be the one `find_path` picks.
+    fn assert_shortest_path(dg: &DirectedGraph, path: &NodeVec, from: &str, to: &str) {
+        let expected = dg.find_path(from, to).unwrap();
+        assert_eq!(path.len(), expected.len());
+        let path = path.as_vec();
+        if !path.is_empty() {
+            assert_eq!(path[0], from);
+            assert_eq!(path[path.len() - 1], to);
+        }
+        assert!(path
+            .windows(2)
+            .all(|edge| dg.has_edge_by_name(edge[0], edge[1])));
+    }
+
+    fn graph() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "D", "E"]).unwrap();
+        builder.add_path(["X", "D"]).unwrap();
+        builder.add_path(["Y", "Z"]).unwrap();
+        builder.add_path(["E", "B"]).unwrap();
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_find_paths_many_to_many() {
+        let dg = graph();
+        let paths = dg
+            .find_paths_many_to_many(["A", "X", "Y"], ["E", "Z", "A"])
+            .unwrap();
+        assert_eq!(paths.len(), 3);
+        assert!(paths.iter().all(|row| row.len() == 3));
+        assert_eq!(paths[0][0], ["A", "B", "C", "D", "E"]);
+        assert!(paths[0][1].is_empty());
+        assert_eq!(paths[0][2], ["A"]);
+        assert_eq!(paths[1][0], ["X", "D", "E"]);
+        assert!(paths[1][1].is_empty());
+        assert_eq!(paths[2][1], ["Y", "Z"]);
+
+        for (i, from) in ["A", "X", "Y"].iter().enumerate() {
+            for (j, to) in ["E", "Z", "A"].iter().enumerate() {
+                assert_shortest_path(&dg, &paths[i][j], from, to);
+            }
+        }
+
+        assert!(dg
+            .find_paths_many_to_many(Vec::<&str>::new(), ["A"])
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            dg.find_paths_many_to_many(["A"], Vec::<&str>::new())
+                .unwrap(),
+            [Vec::<NodeVec>::new()]
+        );
+        assert!(dg.find_paths_many_to_many(["A"], ["Q"]).is_err());
+    }
+
+    #[test]
+    fn dg_find_paths_many_to_many_fewer_targets() {
+        let dg = graph();
+        let from = ["A", "X", "Y", "E"];
+        let to = ["C", "Z"];
+        let paths = dg.find_paths_many_to_many(from, to).unwrap();
+        assert_eq!(paths.len(), 4);
+        assert!(paths.iter().all(|row| row.len() == 2));
+        assert_eq!(paths[0][0], ["A", "B", "C"]);
+        assert_eq!(paths[3][0], ["E", "B", "C"]);
+        assert!(paths[1][1].is_empty());
+        for (i, from) in from.iter().enumerate() {
+            for (j, to) in to.iter().enumerate() {
+                assert_shortest_path(&dg, &paths[i][j], from, to);
+            }
+        }
+
+        assert_eq!(
+            dg.find_paths_many_to_many(["A", "X"], Vec::<&str>::new())
+                .unwrap(),
+            [Vec::<NodeVec>::new(), Vec::new()]
+        );
+    }
+
+    #[test]
+    fn dg_find_paths_many_to_many_many_shortest_paths() {
+        // Every node reaches `i + 1` and `i + 2`, so there are many
+        // shortest paths between most pairs
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..20 {
+            builder.add_edge(i.to_string(), (i + 1).to_string());
+            builder.add_edge(i.to_string(), (i + 2).to_string());
+        }
+        let dg = builder.build_directed();
+
+        let few = ["0", "1"];
+        let many = ["5", "10", "15", "21"];
+        for (from, to) in [(&few[..], &many[..]), (&many[..], &few[..])] {
+            let paths = dg.find_paths_many_to_many(from, to).unwrap();
+            for (i, from) in from.iter().enumerate() {
+                for (j, to) in to.iter().enumerate() {
+                    assert_shortest_path(&dg, &paths[i][j], from, to);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dg_multi_source_shortest_path() {
+        let dg = graph();
+        assert_eq!(
+            dg.multi_source_shortest_path(["A", "X"], "E").unwrap(),
+            ["X", "D", "E"]
+        );
+        assert_eq!(
+            dg.multi_source_shortest_path(["A", "X"], "C").unwrap(),
+            ["A", "B", "C"]
+        );
+        assert_eq!(
+            dg.multi_source_shortest_path(["X", "E"], "C").unwrap(),
+            ["E", "B", "C"]
+        );
+        assert_eq!(
+            dg.multi_source_shortest_path(["A", "D"], "D").unwrap(),
+            ["D"]
+        );
+        assert!(dg
+            .multi_source_shortest_path(["A", "X"], "Z")
+            .unwrap()
+            .is_empty());
+        assert!(dg
+            .multi_source_shortest_path(Vec::<&str>::new(), "Z")
+            .unwrap()
+            .is_empty());
+        assert!(dg.multi_source_shortest_path(["Q"], "Z").is_err());
+    }
+}
@@ -1,14 +1,39 @@
 pub mod acyclic;
+mod articulation;
+mod attributes;
+pub(crate) mod bfs;
 pub mod builder;
+mod centrality;
+pub mod cycles_iter;
 mod debug;
+pub mod diff;
+pub mod disjoint_paths;
+mod distances;
+mod dominators;
+mod eq;
+mod feedback_arc_set;
+mod flow;
 mod get_rel2_on_rel1;
-
-use self::get_rel2_on_rel1::get_values_on_rel_map;
+mod labels;
+mod many_to_many;
+mod multigraph;
+pub mod paths_iter;
+mod reverse;
+mod set_ops;
+mod shortest_paths;
+pub mod stats;
+pub mod undirected;
+pub(crate) mod weighted;
+
+use self::{bfs::BidirectionalBfs, get_rel2_on_rel1::get_values_on_rel_map};
 use crate::{
     prelude::*,
     utils::{
+        edge_labels::EdgeLabels,
+        edge_map::EdgeMap,
         internal_bufs::InternalBufs,
         interner::Resolver,
+        node_attributes::NodeAttributes,
         node_map::{LazySet, NodeMap},
         node_set::NodeVec,
         sym::Sym,
@@ -16,7 +41,10 @@ use crate::{
 };
 use fxhash::FxHashSet;
 use std::{
-    collections::{HashMap, VecDeque}, num::NonZeroUsize, ops::Not, sync::Arc,
+    collections::{HashMap, VecDeque},
+    num::NonZeroUsize,
+    ops::Not,
+    sync::Arc,
 };
 
 // Helper function for constructing the path
@@ -48,6 +76,24 @@ pub struct DirectedGraph {
     /// Key: Child | Value: Parents
     pub(crate) parent_map: NodeMap,
     pub(crate) n_edges: usize,
+    /// Weight of each edge. `None` if the graph is unweighted
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) weights: Option<EdgeMap<f64>>,
+    /// Attributes of each node. `None` if no attribute was set
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) attributes: Option<NodeAttributes>,
+    /// Labels of each edge. `None` if no labeled edge was added
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) labels: Option<EdgeLabels>,
+    /// Ids of the parallel edges between two nodes. Only set on
+    /// multigraphs
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) edge_ids: Option<EdgeMap<Vec<u32>>>,
+    /// Weight of each parallel edge in the same order as
+    /// `edge_ids`. Only set on weighted multigraphs, `weights`
+    /// then holds the lowest of them
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) parallel_weights: Option<EdgeMap<Vec<f64>>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
     pub(crate) buf: InternalBufs,
 }
@@ -61,10 +107,20 @@ impl Clone for DirectedGraph {
         let children_map = self.children_map.clone();
         let parent_map = self.parent_map.clone();
         let n_edges = self.n_edges;
+        let weights = self.weights.clone();
+        let attributes = self.attributes.clone();
+        let labels = self.labels.clone();
+        let edge_ids = self.edge_ids.clone();
+        let parallel_weights = self.parallel_weights.clone();
 
         DirectedGraph {
             interner,
             n_edges,
+            weights,
+            attributes,
+            labels,
+            edge_ids,
+            parallel_weights,
             parent_map,
             children_map,
             nodes,
@@ -94,6 +150,7 @@ impl DirectedGraph {
     impl_buf!(u32x2_vec_0, Vec<(Sym, Sym)>);
     impl_buf!(u32x1_queue_0, VecDeque<Sym>);
     impl_buf!(u32x1_set_0, FxHashSet<Sym>);
+    impl_buf!(bidirectional_bfs_0, BidirectionalBfs);
     impl_buf!(usizex2_queue_0, VecDeque<(usize, usize)>);
 
     #[inline(always)]
@@ -274,6 +331,10 @@ impl DirectedGraph {
             .collect())
     }
 
+    /// Finds a shortest path from `from` to `to`. The search runs
+    /// a BFS from both ends at the same time, following the
+    /// children of `from` and the parents of `to`, and stops when
+    /// they meet. The path is empty if `to` is not reachable.
     pub fn find_path(
         &self,
         from: impl AsRef<str>,
@@ -281,40 +342,16 @@ impl DirectedGraph {
     ) -> GraphInteractionResult<NodeVec> {
         let from = self.get_internal(from)?;
         let to = self.get_internal(to)?;
-
-        if from == to {
-            return Ok(self.resolve_mul_slice(&[from]));
-        }
-
-        let queue = unsafe { self.u32x1_queue_0() };
-        let visited = unsafe { self.u32x1_set_0() };
         let path_buf = unsafe { self.u32x1_vec_0() };
-        let parents = unsafe { self.u32x2_vec_0() }; // To track the path back to the start node
-
-        // Initialize
-        queue.push_back(from);
-        visited.insert(from);
-
-        'outer: while let Some(current) = queue.pop_front() {
-            if let LazySet::Initialized(children) = self.children_map.get(current) {
-                for &child in children.iter() {
-                    if visited.insert(child) {
-                        parents.push((child, current));
-                        if child == to {
-                            // Construct the path and place it in `path_buf`
-                            construct_path(parents, from, to, path_buf);
-                            break 'outer;
-                        }
-                        queue.push_back(child);
-                    }
-                }
-            }
-        }
-
+        let bfs = unsafe { self.bidirectional_bfs_0() };
+        bfs.find_path(&self.children_map, &self.parent_map, &[from], to, path_buf);
         Ok(self.resolve_mul_slice(path_buf))
     }
 
     /// Finds all paths on a DG using BFS
+    ///
+    /// NOTE: Every path is kept in memory. Use
+    /// [`DirectedGraph::paths_iter`] to enumerate them lazily.
     pub fn find_all_paths(
         &self,
         from: impl AsRef<str>,
@@ -358,6 +395,12 @@ impl DirectedGraph {
             .collect())
     }
 
+    /// Returns the nodes in `selected` that do not have a parent
+    /// inside `selected`.
+    ///
+    /// NOTE: Despite the name this is not a lowest common ancestor
+    /// query. For that see
+    /// [`DirectedAcyclicGraph::lowest_common_ancestors`].
     pub fn least_common_parents(
         &self,
         selected: impl IntoIterator<Item = impl AsRef<str>>,
@@ -465,7 +508,18 @@ impl DirectedGraph {
         Ok(self.resolve_mul_slice(roots))
     }
 
+    #[inline]
     fn subset_multi_u32(&self, nodes_subset: &[Sym]) -> DirectedGraph {
+        self.subset_multi_u32_filtered(nodes_subset, |_, _| true)
+    }
+
+    /// Same as `subset_multi_u32` but only follows the edges
+    /// for which `follow(parent, child)` returns `true`.
+    fn subset_multi_u32_filtered(
+        &self,
+        nodes_subset: &[Sym],
+        follow: impl Fn(Sym, Sym) -> bool,
+    ) -> DirectedGraph {
         if nodes_subset.is_empty() {
             return self.clone();
         }
@@ -487,9 +541,18 @@ impl DirectedGraph {
 
             visited.insert(node);
             match self.children_map.get(node) {
-                LazySet::Initialized(children) => children.iter().for_each(|&child| {
-                    queue.push_back((node, child));
-                }),
+                LazySet::Initialized(children) => {
+                    let n_queued = queue.len();
+                    children
+                        .iter()
+                        .filter(|&&child| follow(node, child))
+                        .for_each(|&child| queue.push_back((node, child)));
+                    // Every child was filtered out
+                    if queue.len() == n_queued {
+                        children_map.get_mut(node).into_empty();
+                        leaves.push(node);
+                    }
+                }
                 LazySet::Empty => {
                     children_map.get_mut(node).into_empty();
                     leaves.push(node);
@@ -519,9 +582,18 @@ impl DirectedGraph {
                         leaves.push(node);
                         children_map.get_mut(node).into_empty();
                     }
-                    LazySet::Initialized(children) => children.iter().for_each(|child| {
-                        queue.push_back((node, *child));
-                    }),
+                    LazySet::Initialized(children) => {
+                        let n_queued = queue.len();
+                        children
+                            .iter()
+                            .filter(|&&child| follow(node, child))
+                            .for_each(|&child| queue.push_back((node, child)));
+                        // Every child was filtered out
+                        if queue.len() == n_queued {
+                            leaves.push(node);
+                            children_map.get_mut(node).into_empty();
+                        }
+                    }
                     LazySet::Uninitialized => (),
                 }
             }
@@ -549,6 +621,31 @@ impl DirectedGraph {
             .filter(|&n| parent_map.get(n).is_empty())
             .collect::<Vec<_>>();
 
+        let weights = self
+            .weights
+            .as_ref()
+            .map(|weights| weights.retain_edges(&children_map));
+        let attributes = self
+            .attributes
+            .as_ref()
+            .map(|attributes| attributes.retain_nodes(&nodes));
+        let labels = self
+            .labels
+            .as_ref()
+            .map(|labels| labels.retain_edges(&children_map));
+        let edge_ids = self
+            .edge_ids
+            .as_ref()
+            .map(|edge_ids| edge_ids.retain_edges(&children_map));
+        let parallel_weights = self
+            .parallel_weights
+            .as_ref()
+            .map(|weights| weights.retain_edges(&children_map));
+        // Parallel edges are only visited once
+        if let Some(edge_ids) = &edge_ids {
+            n_edges = edge_ids.iter().map(|(_, _, ids)| ids.len()).sum();
+        }
+
         DirectedGraph {
             interner: Arc::clone(&self.interner),
             nodes,
@@ -557,6 +654,11 @@ impl DirectedGraph {
             n_edges,
             parent_map,
             children_map,
+            weights,
+            attributes,
+            labels,
+            edge_ids,
+            parallel_weights,
             buf: InternalBufs::default(),
         }
     }
@@ -656,6 +758,31 @@ impl DirectedGraph {
             .filter(|&n| parent_map.get(n).is_empty())
             .collect::<Vec<_>>();
 
+        let weights = self
+            .weights
+            .as_ref()
+            .map(|weights| weights.retain_edges(&children_map));
+        let attributes = self
+            .attributes
+            .as_ref()
+            .map(|attributes| attributes.retain_nodes(&nodes));
+        let labels = self
+            .labels
+            .as_ref()
+            .map(|labels| labels.retain_edges(&children_map));
+        let edge_ids = self
+            .edge_ids
+            .as_ref()
+            .map(|edge_ids| edge_ids.retain_edges(&children_map));
+        let parallel_weights = self
+            .parallel_weights
+            .as_ref()
+            .map(|weights| weights.retain_edges(&children_map));
+        // Parallel edges are only visited once
+        if let Some(edge_ids) = &edge_ids {
+            n_edges = edge_ids.iter().map(|(_, _, ids)| ids.len()).sum();
+        }
+
         DirectedGraph {
             interner: Arc::clone(&self.interner),
             nodes,
@@ -664,6 +791,11 @@ impl DirectedGraph {
             n_edges,
             parent_map,
             children_map,
+            weights,
+            attributes,
+            labels,
+            edge_ids,
+            parallel_weights,
             buf: InternalBufs::default(),
         }
     }
@@ -719,6 +851,46 @@ impl DirectedGraph {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns the number of edges in the graph. Parallel edges
+    /// of a multigraph are counted.
+    pub fn n_edges(&self) -> usize {
+        self.n_edges
+    }
+
+    pub fn n_roots(&self) -> usize {
+        self.roots.len()
+    }
+
+    pub fn n_leaves(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns the number of edges pointing to `node`.
+    pub fn in_degree(&self, node: impl AsRef<str>) -> GraphInteractionResult<usize> {
+        Ok(self.in_degree_u32(self.get_internal(node)?))
+    }
+
+    /// Returns the number of edges leaving `node`.
+    pub fn out_degree(&self, node: impl AsRef<str>) -> GraphInteractionResult<usize> {
+        Ok(self.out_degree_u32(self.get_internal(node)?))
+    }
+
+    /// Number of edges entering `node`, counting parallel edges.
+    pub(crate) fn in_degree_u32(&self, node: Sym) -> usize {
+        self.parent_map
+            .values(node)
+            .map(|parent| self.edge_multiplicity_u32(parent, node))
+            .sum()
+    }
+
+    /// Number of edges leaving `node`, counting parallel edges.
+    pub(crate) fn out_degree_u32(&self, node: Sym) -> usize {
+        self.children_map
+            .values(node)
+            .map(|child| self.edge_multiplicity_u32(node, child))
+            .sum()
+    }
 }
 
 #[cfg(test)]
@@ -733,6 +905,18 @@ mod tests {
         assert_eq!(builder.children, [1], "Children is not equal");
     }
 
+    #[test]
+    fn dg_builder_unweighted_edge_keeps_weight() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 3.0);
+        builder.add_edge("A", "B");
+        builder.add_labeled_edge("A", "B", "owns");
+        builder.add_edge("B", "C");
+        let dg = builder.build_directed();
+        assert_eq!(dg.edge_weight("A", "B").unwrap(), Some(3.0));
+        assert_eq!(dg.edge_weight("B", "C").unwrap(), Some(1.0));
+    }
+
     #[test]
     fn dg_builder_add_path() {
         let mut builder = DirectedGraphBuilder::new();
@@ -855,6 +1039,71 @@ mod tests {
         assert_eq!(dg.children(["A"]).unwrap(), ["H", "B"]);
     }
 
+    /// Hop count of a shortest path using a plain forward BFS
+    fn forward_bfs_len(dg: &DirectedGraph, from: &str, to: &str) -> Option<usize> {
+        let mut depth = HashMap::new();
+        let mut queue = VecDeque::from([from.to_string()]);
+        depth.insert(from.to_string(), 0);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                return Some(depth[&node] + 1);
+            }
+            for child in dg.children([node.as_str()]).unwrap().iter() {
+                if !depth.contains_key(child) {
+                    depth.insert(child.to_string(), depth[&node] + 1);
+                    queue.push_back(child.to_string());
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn dg_find_path_same_length_as_forward_bfs() {
+        // Pseudo random graphs with cycles and unreachable pairs
+        let mut state = 0x2545f491u64;
+        let mut next = move |n: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (state >> 33) % n
+        };
+        for _ in 0..10 {
+            let mut builder = DirectedGraphBuilder::new();
+            for _ in 0..80 {
+                builder.add_edge(next(40).to_string(), next(40).to_string());
+            }
+            let dg = builder.build_directed();
+            let nodes = dg.nodes();
+            for from in nodes.iter() {
+                for to in nodes.iter() {
+                    let path = dg.find_path(from, to).unwrap();
+                    let expected = forward_bfs_len(&dg, from, to);
+                    assert_eq!((!path.is_empty()).then(|| path.len()), expected);
+                    if let Some(first) = path.get(0) {
+                        assert_eq!(first, from);
+                        assert_eq!(path.get(path.len() - 1), Some(to));
+                    }
+                    for i in 1..path.len() {
+                        let children = dg.children([path.get(i - 1).unwrap()]).unwrap();
+                        assert!(children.iter().any(|child| Some(child) == path.get(i)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dg_find_path_unreachable() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["D", "C"]).unwrap();
+        builder.add_path(["C", "B"]).unwrap();
+        let dg = builder.build_directed();
+        assert!(dg.find_path("A", "D").unwrap().is_empty());
+        assert!(dg.find_path("C", "A").unwrap().is_empty());
+        assert_eq!(dg.find_path("D", "B").unwrap(), ["D", "C", "B"]);
+        assert_eq!(dg.find_path("A", "A").unwrap(), ["A"]);
+    }
+
     #[test]
     fn dg_find_path_one_to_many() {
         let mut builder = DirectedGraphBuilder::new();
@@ -0,0 +1,199 @@
+use crate::{prelude::*, utils::sym::Sym};
+
+impl DirectedGraph {
+    /// Number of edges going from `parent` to `child`. Assumes
+    /// the edge exists.
+    #[inline]
+    pub(crate) fn edge_multiplicity_u32(&self, parent: Sym, child: Sym) -> usize {
+        match &self.edge_ids {
+            Some(edge_ids) => edge_ids.get(parent, child).map_or(0, Vec::len),
+            None => 1,
+        }
+    }
+
+    /// Sum of the weights of every edge going from `parent` to
+    /// `child`. Assumes the edge exists.
+    pub(crate) fn total_weight_u32(&self, parent: Sym, child: Sym) -> f64 {
+        match self
+            .parallel_weights
+            .as_ref()
+            .and_then(|weights| weights.get(parent, child))
+        {
+            Some(weights) => weights.iter().sum(),
+            None => {
+                self.weight_u32(parent, child) * self.edge_multiplicity_u32(parent, child) as f64
+            }
+        }
+    }
+
+    /// Returns `true` if the graph was built keeping parallel
+    /// edges.
+    pub fn is_multigraph(&self) -> bool {
+        self.edge_ids.is_some()
+    }
+
+    /// Returns the number of edges going from `from` to `to`.
+    ///
+    /// This is `0` or `1` unless the graph is a multigraph.
+    pub fn edge_multiplicity(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<usize> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+        if !self.has_edge_u32(from, to) {
+            return Ok(0);
+        }
+        Ok(self.edge_multiplicity_u32(from, to))
+    }
+
+    /// Returns the ids of the edges going from `from` to `to`. Ids
+    /// follow the order in which edges were added to the builder.
+    ///
+    /// NOTE: Ids are only kept on multigraphs. On any other graph
+    /// the result is empty.
+    pub fn edge_ids(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<&[u32]> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+        Ok(self
+            .edge_ids
+            .as_ref()
+            .and_then(|edge_ids| edge_ids.get(from, to))
+            .map_or(&[], Vec::as_slice))
+    }
+
+    /// Returns the weight of every edge going from `from` to `to`
+    /// in the same order as [`DirectedGraph::edge_ids`]. The
+    /// result is empty if the edge does not exist.
+    ///
+    /// On graphs that are not multigraphs this is the same as
+    /// [`DirectedGraph::edge_weight`].
+    pub fn parallel_edge_weights(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<Vec<f64>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+        if !self.has_edge_u32(from, to) {
+            return Ok(Vec::new());
+        }
+        let weight = self.weight_u32(from, to);
+        Ok(match &self.parallel_weights {
+            Some(weights) => weights.get(from, to).cloned().unwrap_or_default(),
+            None => vec![weight; self.edge_multiplicity_u32(from, to)],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn transfers(multigraph: bool) -> DirectedGraphBuilder {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.set_multigraph(multigraph);
+        builder.add_edge("alice", "bob");
+        builder.add_edge("bob", "carol");
+        builder.add_edge("alice", "bob");
+        builder.add_edge("carol", "dave");
+        builder.add_edge("alice", "bob");
+        builder
+    }
+
+    #[test]
+    fn dg_edge_multiplicity() {
+        let dg = transfers(true).build_directed();
+        assert!(dg.is_multigraph());
+        assert_eq!(dg.n_edges, 5);
+        assert_eq!(dg.edge_multiplicity("alice", "bob").unwrap(), 3);
+        assert_eq!(dg.edge_multiplicity("bob", "carol").unwrap(), 1);
+        assert_eq!(dg.edge_multiplicity("bob", "alice").unwrap(), 0);
+        assert!(dg.edge_multiplicity("alice", "zoe").is_err());
+        assert_eq!(dg.edge_ids("alice", "bob").unwrap(), [0, 2, 4]);
+        assert_eq!(dg.edge_ids("carol", "dave").unwrap(), [3]);
+        assert!(dg.edge_ids("dave", "carol").unwrap().is_empty());
+    }
+
+    #[test]
+    fn dg_parallel_edges_collapse_by_default() {
+        let dg = transfers(false).build_directed();
+        assert!(!dg.is_multigraph());
+        assert_eq!(dg.n_edges, 3);
+        assert_eq!(dg.edge_multiplicity("alice", "bob").unwrap(), 1);
+        assert!(dg.edge_ids("alice", "bob").unwrap().is_empty());
+    }
+
+    #[test]
+    fn dg_subset_keeps_parallel_edges() {
+        let mut builder = transfers(true);
+        builder.add_edge("dave", "erin");
+        builder.add_edge("dave", "erin");
+        let dg = builder.build_directed();
+        let dg2 = dg.subset("carol").unwrap();
+        assert_eq!(dg2.n_edges, 3);
+        assert_eq!(dg2.edge_ids("dave", "erin").unwrap(), [5, 6]);
+        assert_eq!(dg2.edge_multiplicity("carol", "dave").unwrap(), 1);
+    }
+
+    #[test]
+    fn dg_parallel_edge_weights() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.set_multigraph(true);
+        builder.add_weighted_edge("alice", "bob", 5.0);
+        builder.add_edge("bob", "carol");
+        builder.add_weighted_edge("alice", "bob", 2.0);
+        builder.add_weighted_edge("alice", "bob", 7.0);
+        builder.add_weighted_edge("alice", "carol", 4.0);
+        let dg = builder.build_directed();
+        assert_eq!(
+            dg.parallel_edge_weights("alice", "bob").unwrap(),
+            [5.0, 2.0, 7.0]
+        );
+        assert_eq!(dg.parallel_edge_weights("bob", "carol").unwrap(), [1.0]);
+        assert!(dg.parallel_edge_weights("bob", "alice").unwrap().is_empty());
+        assert_eq!(dg.edge_weight("alice", "bob").unwrap(), Some(2.0));
+
+        let (path, cost) = dg
+            .shortest_path_weighted("alice", "carol")
+            .unwrap()
+            .unwrap();
+        assert_eq!(path, ["alice", "bob", "carol"]);
+        assert_eq!(cost, 3.0);
+        assert_eq!(dg.max_flow("alice", "bob").unwrap(), 14.0);
+        assert_eq!(dg.max_flow("alice", "carol").unwrap(), 5.0);
+
+        let dg2 = dg.subset("alice").unwrap();
+        assert_eq!(
+            dg2.parallel_edge_weights("alice", "bob").unwrap(),
+            [5.0, 2.0, 7.0]
+        );
+        let reversed = dg.reversed();
+        assert_eq!(
+            reversed.parallel_edge_weights("bob", "alice").unwrap(),
+            [5.0, 2.0, 7.0]
+        );
+
+        let dg = transfers(true).build_directed();
+        assert_eq!(
+            dg.parallel_edge_weights("alice", "bob").unwrap(),
+            [1.0, 1.0, 1.0]
+        );
+        assert_eq!(dg.max_flow("alice", "bob").unwrap(), 3.0);
+    }
+
+    #[test]
+    fn dag_multigraph() {
+        let dag = transfers(true).build_acyclic().unwrap();
+        assert_eq!(dag.edge_multiplicity("alice", "bob").unwrap(), 3);
+
+        let mut builder = transfers(true);
+        builder.add_edge("dave", "alice");
+        assert!(builder.build_acyclic().is_err());
+    }
+}
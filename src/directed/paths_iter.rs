@@ -0,0 +1,216 @@
+use fxhash::FxHashSet;
+
+use crate::{
+    prelude::*,
+    utils::{node_map::LazySet, node_set::NodeVec, sym::Sym},
+};
+
+/// Lazily enumerates the simple paths between two nodes using an
+/// iterative DFS. Created by [`DirectedGraph::paths_iter`].
+///
+/// Only the current path and the pending children along it are
+/// kept in memory, so it can be used on graphs with too many
+/// paths to collect at once.
+pub struct PathsIter<'a> {
+    graph: &'a DirectedGraph,
+    to: Sym,
+    path: Vec<Sym>,
+    on_path: FxHashSet<Sym>,
+    /// Children left to explore for every node in `path`
+    pending: Vec<Sym>,
+    /// Index in `pending` where the children of each node in
+    /// `path` start
+    pending_starts: Vec<usize>,
+    max_paths: Option<usize>,
+    max_length: Option<usize>,
+    n_found: usize,
+}
+
+impl<'a> PathsIter<'a> {
+    fn new(graph: &'a DirectedGraph, from: Sym, to: Sym) -> Self {
+        let mut iter = PathsIter {
+            graph,
+            to,
+            path: vec![from],
+            on_path: FxHashSet::default(),
+            pending: Vec::new(),
+            pending_starts: vec![0],
+            max_paths: None,
+            max_length: None,
+            n_found: 0,
+        };
+        iter.on_path.insert(from);
+        // If we start on the goal the only path is the node itself,
+        // so we do not push any children to explore.
+        if from != to {
+            graph.children_u32(&[from], &mut iter.pending);
+        }
+        iter
+    }
+
+    /// Stops after `max_paths` paths have been found.
+    pub fn max_paths(mut self, max_paths: usize) -> Self {
+        self.max_paths = Some(max_paths);
+        self
+    }
+
+    /// Only yields paths with at most `max_length` edges.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+}
+
+impl Iterator for PathsIter<'_> {
+    type Item = NodeVec;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self
+            .max_paths
+            .is_some_and(|max_paths| self.n_found >= max_paths)
+        {
+            return None;
+        }
+
+        // Trivial path where `from` and `to` are the same node
+        if self.path.len() == 1 && self.path[0] == self.to {
+            self.n_found += 1;
+            return Some(
+                self.graph
+                    .resolve_mul_slice(&std::mem::take(&mut self.path)),
+            );
+        }
+
+        while let Some(&start) = self.pending_starts.last() {
+            // We explored every child of the last node on the
+            // path, so we backtrack
+            if self.pending.len() <= start {
+                self.pending_starts.pop();
+                if let Some(node) = self.path.pop() {
+                    self.on_path.remove(&node);
+                }
+                continue;
+            }
+
+            let child = match self.pending.pop() {
+                Some(child) => child,
+                None => unsafe { std::hint::unreachable_unchecked() },
+            };
+
+            // Adding `child` would make the path this long
+            let length = self.path.len();
+            if self.on_path.contains(&child)
+                || self
+                    .max_length
+                    .is_some_and(|max_length| length > max_length)
+            {
+                continue;
+            }
+
+            if child == self.to {
+                self.path.push(child);
+                let found = self.graph.resolve_mul_slice(&self.path);
+                self.path.pop();
+                self.n_found += 1;
+                return Some(found);
+            }
+
+            self.path.push(child);
+            self.on_path.insert(child);
+            self.pending_starts.push(self.pending.len());
+            if let LazySet::Initialized(children) = self.graph.children_map.get(child) {
+                self.pending.extend(children.iter().copied());
+            }
+        }
+
+        None
+    }
+}
+
+impl DirectedGraph {
+    /// Returns an iterator over every simple path between two
+    /// nodes. Paths are generated on demand in depth first order,
+    /// use [`PathsIter::max_paths`] and [`PathsIter::max_length`]
+    /// to bound the search.
+    pub fn paths_iter(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<PathsIter<'_>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+        Ok(PathsIter::new(self, from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn graph() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder
+            .add_path(["0", "111", "222", "333", "444", "4"])
+            .unwrap();
+        builder.add_path(["0", "999", "4"]).unwrap();
+        builder.add_path(["0", "1", "2", "3", "4"]).unwrap();
+        builder.add_path(["0", "4"]).unwrap();
+        builder.build_directed()
+    }
+
+    #[test]
+    fn dg_paths_iter_same_paths_as_find_all_paths() {
+        let dg = graph();
+        let paths: Vec<NodeVec> = dg.paths_iter("0", "4").unwrap().collect();
+        let mut from_iter: Vec<Vec<&str>> = paths.iter().map(|path| path.as_vec()).collect();
+        let all_paths = dg.find_all_paths("0", "4").unwrap();
+        let mut from_bfs: Vec<Vec<&str>> = all_paths.iter().map(|path| path.as_vec()).collect();
+        from_iter.sort();
+        from_bfs.sort();
+        assert_eq!(from_iter, from_bfs);
+    }
+
+    #[test]
+    fn dg_paths_iter_limits() {
+        let dg = graph();
+        assert_eq!(dg.paths_iter("0", "4").unwrap().max_paths(2).count(), 2);
+        assert_eq!(dg.paths_iter("0", "4").unwrap().max_paths(0).count(), 0);
+
+        let short: Vec<_> = dg.paths_iter("0", "4").unwrap().max_length(2).collect();
+        assert_eq!(short.len(), 2);
+        assert!(short.iter().all(|path| path.len() <= 3));
+
+        let none: Vec<_> = dg.paths_iter("0", "4").unwrap().max_length(0).collect();
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn dg_paths_iter_with_cycle() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "A"]).unwrap();
+        builder.add_path(["B", "D"]).unwrap();
+        builder.add_path(["C", "D"]).unwrap();
+        let dg = builder.build_directed();
+
+        let paths: Vec<NodeVec> = dg.paths_iter("A", "D").unwrap().collect();
+        let mut paths: Vec<Vec<&str>> = paths.iter().map(|path| path.as_vec()).collect();
+        paths.sort();
+        assert_eq!(paths, vec![vec!["A", "B", "C", "D"], vec!["A", "B", "D"]]);
+
+        assert_eq!(
+            dg.paths_iter("A", "A").unwrap().collect::<Vec<_>>(),
+            [vec!["A"]]
+        );
+    }
+
+    #[test]
+    fn dag_paths_iter_does_not_overflow_the_stack() {
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..100_000 {
+            builder.add_edge(i.to_string(), (i + 1).to_string());
+        }
+        let dag = builder.build_acyclic().unwrap();
+        let paths = dag.find_all_paths("0", "100000").unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].len(), 100_001);
+    }
+}
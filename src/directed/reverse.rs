@@ -0,0 +1,74 @@
+use crate::prelude::*;
+
+impl DirectedGraph {
+    /// Returns the graph with the direction of every edge flipped.
+    /// Roots become leaves and parents become children.
+    ///
+    /// This does not copy the graph, call `clone` first to keep
+    /// the original.
+    pub fn reversed(mut self) -> DirectedGraph {
+        std::mem::swap(&mut self.children_map, &mut self.parent_map);
+        std::mem::swap(&mut self.roots, &mut self.leaves);
+        self.weights = self.weights.map(|weights| weights.reversed());
+        self.labels = self.labels.map(|labels| labels.reversed());
+        self.edge_ids = self.edge_ids.map(|edge_ids| edge_ids.reversed());
+        self.parallel_weights = self.parallel_weights.map(|weights| weights.reversed());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn graph() -> DirectedGraphBuilder {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 2.0);
+        builder.add_labeled_edge("B", "C", "calls");
+        builder.add_path(["A", "D", "C"]).unwrap();
+        builder.add_edge("E", "D");
+        builder
+    }
+
+    #[test]
+    fn dg_reversed() {
+        let dg = graph().build_directed();
+        let rev = dg.clone().reversed();
+
+        assert_eq!(rev.get_all_roots(), dg.get_all_leaves());
+        assert_eq!(rev.get_all_leaves(), dg.get_all_roots());
+        assert_eq!(rev.children(["C"]).unwrap().len(), 2);
+        assert_eq!(rev.find_path("C", "A").unwrap().len(), 3);
+        assert!(rev.find_path("A", "C").unwrap().is_empty());
+
+        assert_eq!(rev.edge_weight("B", "A").unwrap(), Some(2.0));
+        assert_eq!(rev.edge_weight("A", "B").unwrap(), None);
+        assert_eq!(rev.edge_labels("C", "B").unwrap(), ["calls"]);
+        assert!(rev.edge_labels("B", "C").unwrap().is_empty());
+
+        let twice = rev.reversed();
+        assert_eq!(twice.get_all_roots(), dg.get_all_roots());
+        assert_eq!(twice.edge_weight("A", "B").unwrap(), Some(2.0));
+    }
+
+    #[test]
+    fn dg_reversed_subset() {
+        let rev = graph().build_directed().reversed();
+        let subset = rev.subset("C").unwrap();
+        assert_eq!(subset.n_edges, 5);
+        assert_eq!(subset.edge_labels("C", "B").unwrap(), ["calls"]);
+        assert_eq!(subset.edge_weight("B", "A").unwrap(), Some(2.0));
+    }
+
+    #[test]
+    fn dag_reversed() {
+        let dag = graph().build_acyclic().unwrap();
+        let rev = dag.clone().reversed();
+        assert_eq!(rev.get_all_roots(), dag.get_all_leaves());
+        assert_eq!(
+            rev.find_all_paths("C", "A").unwrap().len(),
+            dag.find_all_paths("A", "C").unwrap().len()
+        );
+        assert!(DirectedAcyclicGraph::build(rev.into_inner()).is_ok());
+    }
+}
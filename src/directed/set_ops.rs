@@ -0,0 +1,327 @@
+use crate::{
+    prelude::*,
+    utils::{node_map::LazySet, sym::Sym},
+};
+
+impl DirectedGraph {
+    /// Iterates over every edge as `(parent, child)`.
+    pub(crate) fn edges_u32(&self) -> impl Iterator<Item = (Sym, Sym)> + '_ {
+        self.nodes
+            .iter()
+            .filter_map(|&parent| match self.children_map.get(parent) {
+                LazySet::Initialized(children) => {
+                    Some(children.iter().map(move |&child| (parent, child)))
+                }
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// Returns `true` if the edge `parent -> child` exists, looking
+    /// the nodes up by name.
+    pub(crate) fn has_edge_by_name(&self, parent: &str, child: &str) -> bool {
+        match (self.interner.get(parent), self.interner.get(child)) {
+            (Some(parent), Some(child)) => self.has_edge_u32(parent, child),
+            _ => false,
+        }
+    }
+
+    /// Returns the number of edges going from `parent` to `child`,
+    /// looking the nodes up by name.
+    pub(crate) fn edge_multiplicity_by_name(&self, parent: &str, child: &str) -> usize {
+        match (self.interner.get(parent), self.interner.get(child)) {
+            (Some(parent), Some(child)) if self.has_edge_u32(parent, child) => {
+                self.edge_multiplicity_u32(parent, child)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Adds the edge `parent -> child` to `builder` along with its
+    /// labels and weight. If `builder` is a multigraph every
+    /// parallel edge is added with its own weight.
+    pub(crate) fn copy_edge_into(
+        &self,
+        builder: &mut DirectedGraphBuilder,
+        parent: Sym,
+        child: Sym,
+    ) {
+        let (parent_name, child_name) = (self.resolve(parent), self.resolve(child));
+        if let Some(labels) = &self.labels {
+            for &label in labels.get(parent, child) {
+                let label = unsafe { labels.interner.resolve_unchecked(label) };
+                builder.add_edge_label(parent_name, child_name, label);
+            }
+        }
+        let (n_edges, parallel_weights) = if builder.multigraph {
+            (
+                self.edge_multiplicity_u32(parent, child),
+                self.parallel_weights
+                    .as_ref()
+                    .and_then(|weights| weights.get(parent, child)),
+            )
+        } else {
+            (1, None)
+        };
+        for i in 0..n_edges {
+            // Edges without a weight do not override the one set
+            // by another graph
+            let weight = match parallel_weights {
+                Some(weights) => weights.get(i),
+                None => self
+                    .weights
+                    .as_ref()
+                    .and_then(|weights| weights.get(parent, child)),
+            };
+            match weight {
+                Some(&weight) => builder.add_weighted_edge(parent_name, child_name, weight),
+                None => builder.add_edge(parent_name, child_name),
+            };
+        }
+    }
+
+    /// Copies the attributes of every node into `builder`.
+    pub(crate) fn copy_attributes_into(&self, builder: &mut DirectedGraphBuilder) {
+        if let Some(attributes) = &self.attributes {
+            for &node in &self.nodes {
+                for (key, value) in attributes.get_all(node) {
+                    builder.set_node_attribute(self.resolve(node), key, value.clone());
+                }
+            }
+        }
+    }
+
+    /// Builds a graph with the edges of `self` for which `keep`
+    /// returns `true`, keeping every parallel edge if `self` is a
+    /// multigraph.
+    fn filter_edges_by_name(&self, keep: impl Fn(&str, &str) -> bool) -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.set_multigraph(self.is_multigraph());
+        for (parent, child) in self.edges_u32() {
+            if keep(self.resolve(parent), self.resolve(child)) {
+                self.copy_edge_into(&mut builder, parent, child);
+            }
+        }
+        self.copy_attributes_into(&mut builder);
+        builder.build_directed()
+    }
+
+    /// Returns a graph with every edge of both graphs. Nodes are
+    /// matched by name so the graphs do not need to share an
+    /// interner.
+    ///
+    /// Labels of an edge present in both graphs are merged. When
+    /// both graphs have a weight or an attribute for the same edge
+    /// or node, the value from `self` is kept. An edge weighted in
+    /// only one of them keeps that weight.
+    ///
+    /// NOTE: The result is never a multigraph, parallel edges are
+    /// collapsed.
+    pub fn union(&self, other: &DirectedGraph) -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        for (parent, child) in other.edges_u32() {
+            other.copy_edge_into(&mut builder, parent, child);
+        }
+        for (parent, child) in self.edges_u32() {
+            self.copy_edge_into(&mut builder, parent, child);
+        }
+        other.copy_attributes_into(&mut builder);
+        self.copy_attributes_into(&mut builder);
+        builder.build_directed()
+    }
+
+    /// Returns a graph with the edges present in both graphs.
+    /// Weights, labels and attributes are taken from `self`.
+    ///
+    /// The result is a multigraph if `self` is one, with every
+    /// parallel edge of `self` between nodes connected in `other`.
+    pub fn intersection(&self, other: &DirectedGraph) -> DirectedGraph {
+        self.filter_edges_by_name(|parent, child| other.has_edge_by_name(parent, child))
+    }
+
+    /// Returns a graph with the edges of `self` that are not in
+    /// `other`. Nodes left without edges are dropped.
+    ///
+    /// The result is a multigraph if `self` is one. An edge in
+    /// `other` removes every parallel edge between the same nodes.
+    pub fn difference(&self, other: &DirectedGraph) -> DirectedGraph {
+        self.filter_edges_by_name(|parent, child| !other.has_edge_by_name(parent, child))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn sorted_edges(dg: &DirectedGraph) -> Vec<(String, String)> {
+        let mut edges: Vec<(String, String)> = dg
+            .edges_u32()
+            .map(|(parent, child)| (dg.resolve(parent).into(), dg.resolve(child).into()))
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    fn edges(edges: &[(&str, &str)]) -> Vec<(String, String)> {
+        edges
+            .iter()
+            .map(|&(parent, child)| (parent.into(), child.into()))
+            .collect()
+    }
+
+    fn graphs() -> (DirectedGraph, DirectedGraph) {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_weighted_edge("A", "D", 3.0);
+        builder.set_node_attribute("A", "team", "one");
+        let first = builder.build_directed();
+
+        // Nodes are interned in a different order
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["X", "C"]).unwrap();
+        builder.add_weighted_edge("A", "D", 5.0);
+        builder.add_labeled_edge("A", "B", "owns");
+        builder.set_node_attribute("A", "team", "two");
+        builder.set_node_attribute("X", "team", "two");
+        let second = builder.build_directed();
+
+        (first, second)
+    }
+
+    #[test]
+    fn dg_union() {
+        let (first, second) = graphs();
+        let union = first.union(&second);
+        assert_eq!(
+            sorted_edges(&union),
+            edges(&[("A", "B"), ("A", "D"), ("B", "C"), ("X", "C")])
+        );
+        assert_eq!(union.edge_weight("A", "D").unwrap(), Some(3.0));
+        assert_eq!(union.edge_weight("A", "B").unwrap(), Some(1.0));
+        assert_eq!(union.edge_labels("A", "B").unwrap(), ["owns"]);
+        assert_eq!(
+            union.node_attribute("A", "team").unwrap().unwrap().as_str(),
+            Some("one")
+        );
+        assert_eq!(
+            union.node_attribute("X", "team").unwrap().unwrap().as_str(),
+            Some("two")
+        );
+    }
+
+    #[test]
+    fn dg_union_keeps_weights_only_in_other() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_labeled_edge("A", "B", "owns");
+        let unweighted = builder.build_directed();
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 4.0);
+        builder.add_labeled_edge("A", "B", "uses");
+        builder.add_weighted_edge("C", "D", 2.0);
+        let weighted = builder.build_directed();
+
+        for union in [unweighted.union(&weighted), weighted.union(&unweighted)] {
+            assert_eq!(union.edge_weight("A", "B").unwrap(), Some(4.0));
+            assert_eq!(union.edge_weight("B", "C").unwrap(), Some(1.0));
+            assert_eq!(union.edge_weight("C", "D").unwrap(), Some(2.0));
+            let labels = union.edge_labels("A", "B").unwrap();
+            let mut labels = labels.to_vec();
+            labels.sort();
+            assert_eq!(labels, ["owns", "uses"]);
+        }
+    }
+
+    #[test]
+    fn dg_intersection() {
+        let (first, second) = graphs();
+        let intersection = first.intersection(&second);
+        assert_eq!(
+            sorted_edges(&intersection),
+            edges(&[("A", "B"), ("A", "D")])
+        );
+        assert_eq!(intersection.edge_weight("A", "D").unwrap(), Some(3.0));
+        assert!(intersection.edge_labels("A", "B").unwrap().is_empty());
+        assert!(intersection.get_internal("C").is_err());
+    }
+
+    #[test]
+    fn dg_difference() {
+        let (first, second) = graphs();
+        assert_eq!(
+            sorted_edges(&first.difference(&second)),
+            edges(&[("B", "C")])
+        );
+        assert_eq!(
+            sorted_edges(&second.difference(&first)),
+            edges(&[("X", "C")])
+        );
+        assert!(sorted_edges(&first.difference(&first)).is_empty());
+    }
+
+    #[test]
+    fn dg_intersection_and_difference_keep_parallel_edges() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.set_multigraph(true);
+        builder.add_weighted_edge("A", "B", 2.0);
+        builder.add_labeled_edge("A", "B", "owns");
+        builder.add_weighted_edge("A", "B", 3.0);
+        builder.add_weighted_edge("B", "C", 4.0);
+        builder.add_edge("B", "C");
+        builder.add_edge("C", "D");
+        let multigraph = builder.build_directed();
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "X"]).unwrap();
+        builder.add_path(["C", "D"]).unwrap();
+        let other = builder.build_directed();
+
+        let intersection = multigraph.intersection(&other);
+        assert!(intersection.is_multigraph());
+        assert_eq!(intersection.n_edges(), 4);
+        assert_eq!(
+            intersection.parallel_edge_weights("A", "B").unwrap(),
+            [2.0, 1.0, 3.0]
+        );
+        assert_eq!(intersection.edge_labels("A", "B").unwrap(), ["owns"]);
+
+        let difference = multigraph.difference(&other);
+        assert!(difference.is_multigraph());
+        assert_eq!(difference.n_edges(), 2);
+        assert_eq!(
+            difference.parallel_edge_weights("B", "C").unwrap(),
+            [4.0, 1.0]
+        );
+        assert_eq!(multigraph.difference(&multigraph).n_edges(), 0);
+
+        // Union still collapses parallel edges
+        assert_eq!(
+            multigraph
+                .union(&other)
+                .edge_multiplicity("A", "B")
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn dag_union() {
+        let (first, second) = graphs();
+        let first = DirectedAcyclicGraph::build(first).unwrap();
+        let second = DirectedAcyclicGraph::build(second).unwrap();
+        let union = first.union(&second).unwrap();
+        let roots = union.get_all_roots();
+        let mut roots = roots.as_vec();
+        roots.sort();
+        assert_eq!(roots, ["A", "X"]);
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["C", "A"]).unwrap();
+        let cycle = builder.build_acyclic().unwrap();
+        assert!(first.union(&cycle).is_err());
+
+        let difference = first.difference(&second);
+        assert_eq!(difference.get_all_roots(), ["B"]);
+    }
+}
@@ -0,0 +1,261 @@
+use fxhash::{FxHashMap, FxHashSet};
+
+use crate::{
+    prelude::*,
+    utils::{node_map::LazySet, node_set::NodeVec, sym::Sym},
+};
+
+impl DirectedGraph {
+    fn path_cost_u32(&self, path: &[Sym]) -> f64 {
+        path.windows(2)
+            .map(|edge| self.weight_u32(edge[0], edge[1]))
+            .sum()
+    }
+
+    /// Finds every path with the minimum number of edges between
+    /// two nodes.
+    ///
+    /// The result is empty if `to` is not reachable from `from`.
+    pub fn find_all_shortest_paths(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<Vec<NodeVec>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+
+        if from == to {
+            return Ok(vec![self.resolve_mul_slice(&[from])]);
+        }
+
+        // Every node maps to the parents it can be reached through
+        // on a shortest path
+        let mut predecessors = FxHashMap::<Sym, Vec<Sym>>::default();
+        predecessors.insert(from, Vec::new());
+        let mut level = vec![from];
+        let mut next_level = Vec::new();
+
+        while !level.is_empty() && !predecessors.contains_key(&to) {
+            let mut discovered = FxHashMap::<Sym, Vec<Sym>>::default();
+            for &node in &level {
+                if let LazySet::Initialized(children) = self.children_map.get(node) {
+                    for &child in children.iter() {
+                        if predecessors.contains_key(&child) {
+                            continue;
+                        }
+                        let parents = discovered.entry(child).or_default();
+                        if parents.is_empty() {
+                            next_level.push(child);
+                        }
+                        parents.push(node);
+                    }
+                }
+            }
+            predecessors.extend(discovered);
+            std::mem::swap(&mut level, &mut next_level);
+            next_level.clear();
+        }
+
+        let mut paths = Vec::new();
+        if !predecessors.contains_key(&to) {
+            return Ok(paths);
+        }
+
+        // Walk the predecessors back from `to`. The stack holds the
+        // node and how deep in the current path it is.
+        let mut path = Vec::new();
+        let mut stack = vec![(to, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            path.truncate(depth);
+            path.push(node);
+            if node == from {
+                let mut found = path.clone();
+                found.reverse();
+                paths.push(self.resolve_mul_slice(&found));
+                continue;
+            }
+            for &parent in predecessors[&node].iter().rev() {
+                stack.push((parent, depth + 1));
+            }
+        }
+
+        Ok(paths)
+    }
+
+    /// Finds up to `k` loopless paths between two nodes ordered by
+    /// their total weight using Yen's algorithm. On unweighted
+    /// graphs this is the number of edges.
+    ///
+    /// Returns an error if a negative weight is found while
+    /// exploring the graph.
+    pub fn find_k_shortest_paths(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+        k: usize,
+    ) -> GraphInteractionResult<Vec<NodeVec>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut shortest: Vec<Vec<Sym>> = match self.dijkstra_u32(from, to)? {
+            Some((path, _)) => vec![path],
+            None => return Ok(Vec::new()),
+        };
+        let mut candidates = Vec::<(f64, Vec<Sym>)>::new();
+        let mut seen = FxHashSet::<Vec<Sym>>::default();
+        seen.insert(shortest[0].clone());
+
+        while shortest.len() < k {
+            let previous = &shortest[shortest.len() - 1];
+
+            for i in 0..previous.len() - 1 {
+                let spur = previous[i];
+                let root = &previous[..=i];
+
+                // Remove the edges that would lead us to a path we
+                // already found and the nodes on the root path so
+                // the new path stays loopless.
+                let blocked_edges: FxHashSet<(Sym, Sym)> = shortest
+                    .iter()
+                    .filter(|path| path.len() > i + 1 && &path[..=i] == root)
+                    .map(|path| (path[i], path[i + 1]))
+                    .collect();
+                let blocked_nodes: FxHashSet<Sym> = root[..i].iter().copied().collect();
+
+                let spur_path = self.dijkstra_filtered_u32(spur, to, |parent, child| {
+                    blocked_nodes.contains(&child) || blocked_edges.contains(&(parent, child))
+                })?;
+
+                if let Some((spur_path, spur_cost)) = spur_path {
+                    let mut path = root[..i].to_vec();
+                    path.extend_from_slice(&spur_path);
+                    if seen.insert(path.clone()) {
+                        candidates.push((self.path_cost_u32(root) + spur_cost, path));
+                    }
+                }
+            }
+
+            let best = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(_, (a_cost, a)), (_, (b_cost, b))| {
+                    a_cost.total_cmp(b_cost).then_with(|| a.len().cmp(&b.len()))
+                })
+                .map(|(i, _)| i);
+
+            match best {
+                Some(best) => shortest.push(candidates.swap_remove(best).1),
+                None => break,
+            }
+        }
+
+        Ok(shortest
+            .iter()
+            .map(|path| self.resolve_mul_slice(path))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn dg_find_all_shortest_paths() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "D", "E"]).unwrap();
+        builder.add_path(["A", "C", "D"]).unwrap();
+        builder.add_path(["A", "F", "G", "H", "E"]).unwrap();
+        builder.add_path(["C", "E"]).unwrap();
+        builder.add_path(["E", "A"]).unwrap();
+        let dg = builder.build_directed();
+
+        let paths = dg.find_all_shortest_paths("A", "D").unwrap();
+        let mut paths: Vec<Vec<&str>> = paths.iter().map(|path| path.as_vec()).collect();
+        paths.sort();
+        assert_eq!(paths, vec![vec!["A", "B", "D"], vec!["A", "C", "D"]]);
+
+        assert_eq!(
+            dg.find_all_shortest_paths("A", "E").unwrap(),
+            [vec!["A", "C", "E"]]
+        );
+        assert_eq!(dg.find_all_shortest_paths("A", "A").unwrap(), [vec!["A"]]);
+        assert!(dg.find_all_shortest_paths("A", "Z").is_err());
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B"]).unwrap();
+        builder.add_path(["C", "D"]).unwrap();
+        let dg = builder.build_directed();
+        assert!(dg.find_all_shortest_paths("A", "D").unwrap().is_empty());
+    }
+
+    #[test]
+    fn dg_find_all_shortest_paths_same_length_as_find_path() {
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..30 {
+            builder.add_edge(i.to_string(), (i + 1).to_string());
+            builder.add_edge(i.to_string(), (i + 2).to_string());
+        }
+        let dg = builder.build_directed();
+        let path = dg.find_path("0", "30").unwrap();
+        let paths = dg.find_all_shortest_paths("0", "30").unwrap();
+        assert!(paths.iter().all(|p| p.len() == path.len()));
+        assert!(paths.contains(&path));
+    }
+
+    #[test]
+    fn dg_find_k_shortest_paths() {
+        // Example from the Wikipedia article on Yen's algorithm
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("C", "D", 3.0);
+        builder.add_weighted_edge("C", "E", 2.0);
+        builder.add_weighted_edge("D", "F", 4.0);
+        builder.add_weighted_edge("E", "D", 1.0);
+        builder.add_weighted_edge("E", "F", 2.0);
+        builder.add_weighted_edge("E", "G", 3.0);
+        builder.add_weighted_edge("F", "G", 2.0);
+        builder.add_weighted_edge("F", "H", 1.0);
+        builder.add_weighted_edge("G", "H", 2.0);
+        let dg = builder.build_directed();
+
+        let paths = dg.find_k_shortest_paths("C", "H", 3).unwrap();
+        assert_eq!(
+            paths,
+            [
+                vec!["C", "E", "F", "H"],
+                vec!["C", "E", "G", "H"],
+                vec!["C", "D", "F", "H"],
+            ]
+        );
+
+        let paths = dg.find_k_shortest_paths("C", "H", 100).unwrap();
+        assert_eq!(paths.len(), 7);
+
+        assert!(dg.find_k_shortest_paths("C", "H", 0).unwrap().is_empty());
+        assert!(dg.find_k_shortest_paths("H", "C", 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn dg_find_k_shortest_paths_unweighted() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["0", "1", "2", "3", "4"]).unwrap();
+        builder.add_path(["0", "9", "4"]).unwrap();
+        builder.add_path(["0", "4"]).unwrap();
+        builder.add_path(["4", "0"]).unwrap();
+        let dg = builder.build_directed();
+
+        let paths = dg.find_k_shortest_paths("0", "4", 5).unwrap();
+        assert_eq!(
+            paths,
+            [
+                vec!["0", "4"],
+                vec!["0", "9", "4"],
+                vec!["0", "1", "2", "3", "4"]
+            ]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+
+/// Summary of the shape of a graph. Created by
+/// [`DirectedGraph::stats`].
+///
+/// Edge counts and degrees count every parallel edge of a
+/// multigraph, the same as [`DirectedGraph::n_edges`] and
+/// [`DirectedGraph::in_degree`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    pub n_nodes: usize,
+    pub n_edges: usize,
+    pub n_roots: usize,
+    pub n_leaves: usize,
+    /// Highest BFS depth reached from the roots, that is the
+    /// most edges on the shortest path from the closest root to
+    /// any node. Nodes only reachable through a cycle are not
+    /// counted. Use [`DirectedAcyclicGraph::longest_path`] for
+    /// the longest path instead
+    pub depth: usize,
+    /// Highest in-degree of a node
+    pub max_fan_in: usize,
+    /// Highest out-degree of a node
+    pub max_fan_out: usize,
+    /// Number of edges over the number of possible edges between
+    /// distinct nodes. Can be above `1.0` on multigraphs
+    pub density: f64,
+    /// `in_degree_histogram[d]` is the number of nodes with an
+    /// in-degree of `d`
+    pub in_degree_histogram: Vec<usize>,
+    /// `out_degree_histogram[d]` is the number of nodes with an
+    /// out-degree of `d`
+    pub out_degree_histogram: Vec<usize>,
+    /// Number of weakly connected components
+    pub n_components: usize,
+}
+
+impl DirectedGraph {
+    /// Returns the highest number of edges on a shortest path
+    /// from any root.
+    fn depth_from_roots_u32(&self) -> usize {
+        let mut distances = vec![usize::MAX; self.interner.len()];
+        let mut queue = VecDeque::new();
+        for &root in &self.roots {
+            distances[root.into_usize()] = 0;
+            queue.push_back(root);
+        }
+        let mut depth = 0;
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node.into_usize()];
+            depth = depth.max(distance);
+            for child in self.children_map.values(node) {
+                if distances[child.into_usize()] == usize::MAX {
+                    distances[child.into_usize()] = distance + 1;
+                    queue.push_back(child);
+                }
+            }
+        }
+        depth
+    }
+
+    /// Computes summary statistics of the graph. The same
+    /// statistics are returned for a [`DirectedAcyclicGraph`].
+    pub fn stats(&self) -> GraphStats {
+        let n_nodes = self.nodes.len();
+        let in_degree_histogram = self.in_degree_distribution();
+        let out_degree_histogram = self.out_degree_distribution();
+        let density = if n_nodes > 1 {
+            self.n_edges as f64 / (n_nodes * (n_nodes - 1)) as f64
+        } else {
+            0.0
+        };
+        GraphStats {
+            n_nodes,
+            n_edges: self.n_edges,
+            n_roots: self.roots.len(),
+            n_leaves: self.leaves.len(),
+            depth: self.depth_from_roots_u32(),
+            max_fan_in: in_degree_histogram.len().saturating_sub(1),
+            max_fan_out: out_degree_histogram.len().saturating_sub(1),
+            density,
+            in_degree_histogram,
+            out_degree_histogram,
+            n_components: self.as_undirected().connected_components().len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn builder() -> DirectedGraphBuilder {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C", "D"]).unwrap();
+        builder.add_path(["A", "D"]).unwrap();
+        builder.add_path(["E", "D"]).unwrap();
+        builder.add_path(["X", "Y"]).unwrap();
+        builder
+    }
+
+    #[test]
+    fn dg_degrees() {
+        let dg = builder().build_directed();
+        assert_eq!(dg.in_degree("D").unwrap(), 3);
+        assert_eq!(dg.out_degree("D").unwrap(), 0);
+        assert_eq!(dg.out_degree("A").unwrap(), 2);
+        assert_eq!(dg.in_degree("A").unwrap(), 0);
+        assert!(dg.in_degree("Z").is_err());
+        assert_eq!(dg.n_edges(), 6);
+        assert_eq!(dg.n_roots(), 3);
+        assert_eq!(dg.n_leaves(), 2);
+
+        let mut builder = builder();
+        builder.set_multigraph(true);
+        builder.add_edge("A", "D");
+        let dg = builder.build_directed();
+        assert_eq!(dg.in_degree("D").unwrap(), 4);
+        assert_eq!(dg.out_degree("A").unwrap(), 3);
+        assert_eq!(dg.n_edges(), 7);
+    }
+
+    #[test]
+    fn dg_stats() {
+        let dg = builder().build_directed();
+        let stats = dg.stats();
+        assert_eq!(stats.n_nodes, 7);
+        assert_eq!(stats.n_edges, 6);
+        assert_eq!(stats.n_roots, 3);
+        assert_eq!(stats.n_leaves, 2);
+        // A -> D is a shortcut over A -> B -> C -> D
+        assert_eq!(stats.depth, 2);
+        assert_eq!(stats.max_fan_in, 3);
+        assert_eq!(stats.max_fan_out, 2);
+        assert!((stats.density - 6.0 / 42.0).abs() < 1e-9);
+        assert_eq!(stats.in_degree_histogram, [3, 3, 0, 1]);
+        assert_eq!(stats.out_degree_histogram, [2, 4, 1]);
+        assert_eq!(stats.n_components, 2);
+    }
+
+    #[test]
+    fn dg_stats_multigraph() {
+        let mut builder = builder();
+        builder.set_multigraph(true);
+        builder.add_edge("A", "D");
+        builder.add_edge("A", "D");
+        let dg = builder.build_directed();
+        let stats = dg.stats();
+        assert_eq!(stats.n_edges, 8);
+        assert_eq!(stats.max_fan_in, dg.in_degree("D").unwrap());
+        assert_eq!(stats.max_fan_in, 5);
+        assert_eq!(stats.max_fan_out, dg.out_degree("A").unwrap());
+        assert_eq!(stats.max_fan_out, 4);
+        assert!((stats.density - 8.0 / 42.0).abs() < 1e-9);
+        assert_eq!(stats.in_degree_histogram, [3, 3, 0, 0, 0, 1]);
+        assert_eq!(stats.out_degree_histogram, [2, 4, 0, 0, 1]);
+        // Every edge is counted once on each side
+        for histogram in [&stats.in_degree_histogram, &stats.out_degree_histogram] {
+            let n_edges: usize = histogram
+                .iter()
+                .enumerate()
+                .map(|(degree, count)| degree * count)
+                .sum();
+            assert_eq!(n_edges, stats.n_edges);
+        }
+    }
+
+    #[test]
+    fn dag_stats() {
+        let dag = builder().build_acyclic().unwrap();
+        let stats = dag.stats();
+        assert_eq!(stats, builder().build_directed().stats());
+        // The depth is not the longest path A -> B -> C -> D
+        assert_eq!(stats.depth, 2);
+        assert_eq!(dag.longest_path().len() - 1, 3);
+        assert_eq!(stats.n_components, 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn dg_stats_serde() {
+        let stats = builder().build_directed().stats();
+        let json = serde_json::to_string(&stats).unwrap();
+        let de_stats: GraphStats = serde_json::from_str(&json).unwrap();
+        assert_eq!(stats, de_stats);
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+
+use fxhash::{FxHashMap, FxHashSet};
+
+use crate::{
+    prelude::*,
+    utils::{node_map::LazySet, node_set::NodeVec, sym::Sym},
+};
+
+/// Read only view of a [`DirectedGraph`] where every edge can be
+/// walked in both directions. Created by
+/// [`DirectedGraph::as_undirected`].
+#[derive(Clone, Copy)]
+pub struct UndirectedView<'a> {
+    graph: &'a DirectedGraph,
+}
+
+impl<'a> UndirectedView<'a> {
+    /// Iterates over the children and the parents of `node`. A
+    /// node connected in both directions is yielded twice.
+    #[inline]
+    fn neighbors_u32(&self, node: Sym) -> impl Iterator<Item = Sym> + 'a {
+        let children = match self.graph.children_map.get(node) {
+            LazySet::Initialized(children) => Some(children.iter().copied()),
+            _ => None,
+        };
+        let parents = match self.graph.parent_map.get(node) {
+            LazySet::Initialized(parents) => Some(parents.iter().copied()),
+            _ => None,
+        };
+        children
+            .into_iter()
+            .flatten()
+            .chain(parents.into_iter().flatten())
+    }
+
+    /// Visits every node reachable from `start` in BFS order
+    /// until `radius` hops are reached. Returns the nodes and the
+    /// node they were discovered from.
+    fn bfs_u32(
+        &self,
+        start: Sym,
+        radius: Option<usize>,
+        goal: Option<Sym>,
+    ) -> (Vec<Sym>, FxHashMap<Sym, Sym>) {
+        let mut order = vec![start];
+        let mut prev = FxHashMap::default();
+        let mut visited = FxHashSet::default();
+        visited.insert(start);
+        let mut queue = VecDeque::new();
+        queue.push_back((start, 0));
+
+        'queue: while let Some((node, depth)) = queue.pop_front() {
+            if radius.is_some_and(|radius| depth >= radius) {
+                continue;
+            }
+            for neighbor in self.neighbors_u32(node) {
+                if visited.insert(neighbor) {
+                    order.push(neighbor);
+                    prev.insert(neighbor, node);
+                    if goal == Some(neighbor) {
+                        break 'queue;
+                    }
+                    queue.push_back((neighbor, depth + 1));
+                }
+            }
+        }
+
+        (order, prev)
+    }
+
+    /// Returns the nodes connected to `node` through an edge in
+    /// any direction, without duplicates.
+    pub fn neighbors(&self, node: impl AsRef<str>) -> GraphInteractionResult<NodeVec> {
+        let node = self.graph.get_internal(node)?;
+        let mut seen = FxHashSet::default();
+        let neighbors: Vec<Sym> = self
+            .neighbors_u32(node)
+            .filter(|&neighbor| seen.insert(neighbor))
+            .collect();
+        Ok(self.graph.resolve_mul_slice(&neighbors))
+    }
+
+    /// Returns every node at most `radius` hops away from `node`
+    /// ignoring the direction of the edges. The result includes
+    /// `node` and is ordered by distance.
+    pub fn bfs_within(
+        &self,
+        node: impl AsRef<str>,
+        radius: usize,
+    ) -> GraphInteractionResult<NodeVec> {
+        let node = self.graph.get_internal(node)?;
+        let (order, _) = self.bfs_u32(node, Some(radius), None);
+        Ok(self.graph.resolve_mul_slice(&order))
+    }
+
+    /// Returns the weakly connected components of the graph. Every
+    /// component starts with its first node in the graph's node
+    /// order.
+    pub fn connected_components(&self) -> Vec<NodeVec> {
+        let mut assigned = FxHashSet::default();
+        let mut components = Vec::new();
+        for &node in &self.graph.nodes {
+            if assigned.contains(&node) {
+                continue;
+            }
+            let (component, _) = self.bfs_u32(node, None, None);
+            assigned.extend(component.iter().copied());
+            components.push(self.graph.resolve_mul_slice(&component));
+        }
+        components
+    }
+
+    /// Finds a path with the fewest edges between two nodes
+    /// ignoring the direction of the edges.
+    ///
+    /// The result is empty if the nodes are not connected.
+    pub fn shortest_path(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<NodeVec> {
+        let from = self.graph.get_internal(from)?;
+        let to = self.graph.get_internal(to)?;
+
+        let (_, prev) = self.bfs_u32(from, None, Some(to));
+        if from != to && !prev.contains_key(&to) {
+            return Ok(self.graph.resolve_mul_slice(&[]));
+        }
+
+        let mut path = vec![to];
+        let mut current = to;
+        while let Some(&node) = prev.get(&current) {
+            path.push(node);
+            current = node;
+        }
+        path.reverse();
+        Ok(self.graph.resolve_mul_slice(&path))
+    }
+}
+
+impl DirectedGraph {
+    /// Returns a view of the graph that ignores the direction of
+    /// the edges.
+    pub fn as_undirected(&self) -> UndirectedView<'_> {
+        UndirectedView { graph: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn graph() -> DirectedGraph {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        builder.add_path(["D", "B"]).unwrap();
+        builder.add_path(["D", "E"]).unwrap();
+        builder.add_path(["C", "A"]).unwrap();
+        builder.add_path(["X", "Y"]).unwrap();
+        builder.build_directed()
+    }
+
+    fn sorted(nodes: NodeVec) -> Vec<String> {
+        let mut nodes: Vec<String> = nodes.iter().map(|node| node.to_string()).collect();
+        nodes.sort();
+        nodes
+    }
+
+    #[test]
+    fn undirected_neighbors() {
+        let dg = graph();
+        let view = dg.as_undirected();
+        assert_eq!(sorted(view.neighbors("B").unwrap()), ["A", "C", "D"]);
+        // A -> B and C -> A, but also B -> C so A only shows once
+        assert_eq!(sorted(view.neighbors("A").unwrap()), ["B", "C"]);
+        assert_eq!(sorted(view.neighbors("Y").unwrap()), ["X"]);
+        assert!(view.neighbors("Z").is_err());
+    }
+
+    #[test]
+    fn undirected_bfs_within() {
+        let dg = graph();
+        let view = dg.as_undirected();
+        assert_eq!(view.bfs_within("E", 0).unwrap(), ["E"]);
+        assert_eq!(sorted(view.bfs_within("E", 1).unwrap()), ["D", "E"]);
+        assert_eq!(sorted(view.bfs_within("E", 2).unwrap()), ["B", "D", "E"]);
+        assert_eq!(
+            sorted(view.bfs_within("E", 10).unwrap()),
+            ["A", "B", "C", "D", "E"]
+        );
+    }
+
+    #[test]
+    fn undirected_connected_components() {
+        let dg = graph();
+        let mut components: Vec<Vec<String>> = dg
+            .as_undirected()
+            .connected_components()
+            .into_iter()
+            .map(sorted)
+            .collect();
+        components.sort();
+        assert_eq!(
+            components,
+            vec![vec!["A", "B", "C", "D", "E"], vec!["X", "Y"]]
+        );
+    }
+
+    #[test]
+    fn undirected_shortest_path() {
+        let dg = graph();
+        let view = dg.as_undirected();
+        assert_eq!(view.shortest_path("E", "A").unwrap(), ["E", "D", "B", "A"]);
+        assert_eq!(view.shortest_path("C", "A").unwrap(), ["C", "A"]);
+        assert_eq!(view.shortest_path("A", "A").unwrap(), ["A"]);
+        assert!(view.shortest_path("A", "X").unwrap().is_empty());
+        // The directed graph has no path from E to A
+        assert!(dg.find_path("E", "A").unwrap().is_empty());
+    }
+}
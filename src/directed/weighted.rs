@@ -0,0 +1,278 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{
+    prelude::*,
+    utils::{node_map::LazySet, node_set::NodeVec, sym::Sym},
+};
+
+/// Entry of the priority queue used by Dijkstra's algorithm.
+/// The ordering is reversed so that `BinaryHeap` pops the
+/// lowest cost first.
+#[derive(Clone, Copy)]
+pub(crate) struct Candidate {
+    pub(crate) cost: f64,
+    pub(crate) node: Sym,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+// Helper function for constructing a path from a
+// predecessor table
+pub(crate) fn construct_path_from_prev(prev: &[Sym], goal_id: Sym) -> Vec<Sym> {
+    let mut path = vec![goal_id];
+    let mut current_id = goal_id;
+    while !prev[current_id.into_usize()].is_reserved() {
+        current_id = prev[current_id.into_usize()];
+        path.push(current_id);
+    }
+    path.reverse();
+    path
+}
+
+impl DirectedGraph {
+    #[inline]
+    pub(crate) fn weight_u32(&self, parent: Sym, child: Sym) -> f64 {
+        match &self.weights {
+            Some(weights) => weights.get(parent, child).copied().unwrap_or(1.0),
+            None => 1.0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn has_edge_u32(&self, parent: Sym, child: Sym) -> bool {
+        match self.children_map.get(parent) {
+            LazySet::Initialized(children) => children.contains(&child),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if any edge was added with
+    /// [`DirectedGraphBuilder::add_weighted_edge`].
+    pub fn is_weighted(&self) -> bool {
+        self.weights.is_some()
+    }
+
+    /// Returns the weight of the edge `from -> to` or `None` if
+    /// the edge does not exist. Edges on unweighted graphs have
+    /// a weight of `1.0`.
+    ///
+    /// On multigraphs this is the lowest weight of the parallel
+    /// edges.
+    pub fn edge_weight(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<Option<f64>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+        Ok(self
+            .has_edge_u32(from, to)
+            .then(|| self.weight_u32(from, to)))
+    }
+
+    #[inline]
+    pub(crate) fn dijkstra_u32(
+        &self,
+        from: Sym,
+        to: Sym,
+    ) -> GraphInteractionResult<Option<(Vec<Sym>, f64)>> {
+        self.dijkstra_filtered_u32(from, to, |_, _| false)
+    }
+
+    /// Dijkstra's algorithm ignoring every edge for which
+    /// `is_blocked(parent, child)` returns `true`.
+    pub(crate) fn dijkstra_filtered_u32(
+        &self,
+        from: Sym,
+        to: Sym,
+        is_blocked: impl Fn(Sym, Sym) -> bool,
+    ) -> GraphInteractionResult<Option<(Vec<Sym>, f64)>> {
+        let mut dist = vec![f64::INFINITY; self.interner.len()];
+        let mut prev = vec![Sym::RESERVED; self.interner.len()];
+        let mut heap = BinaryHeap::new();
+
+        dist[from.into_usize()] = 0.0;
+        heap.push(Candidate {
+            cost: 0.0,
+            node: from,
+        });
+
+        while let Some(Candidate { cost, node }) = heap.pop() {
+            if node == to {
+                return Ok(Some((construct_path_from_prev(&prev, to), cost)));
+            }
+            // We already found a better way to get here
+            if cost > dist[node.into_usize()] {
+                continue;
+            }
+            if let LazySet::Initialized(children) = self.children_map.get(node) {
+                for &child in children.iter() {
+                    if is_blocked(node, child) {
+                        continue;
+                    }
+                    let weight = self.weight_u32(node, child);
+                    if weight.is_nan() {
+                        return Err(GraphInteractionError::NaNWeight(
+                            self.resolve(node).into(),
+                            self.resolve(child).into(),
+                        ));
+                    }
+                    if weight < 0.0 {
+                        return Err(GraphInteractionError::NegativeWeight(
+                            self.resolve(node).into(),
+                            self.resolve(child).into(),
+                        ));
+                    }
+                    let next = cost + weight;
+                    if next < dist[child.into_usize()] {
+                        dist[child.into_usize()] = next;
+                        prev[child.into_usize()] = node;
+                        heap.push(Candidate {
+                            cost: next,
+                            node: child,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Finds the path with the lowest total weight between two
+    /// nodes using Dijkstra's algorithm. Returns the path and its
+    /// cost or `None` if `to` is not reachable from `from`.
+    ///
+    /// Returns an error if a negative or NaN weight is found
+    /// while exploring the graph.
+    pub fn shortest_path_weighted(
+        &self,
+        from: impl AsRef<str>,
+        to: impl AsRef<str>,
+    ) -> GraphInteractionResult<Option<(NodeVec, f64)>> {
+        let from = self.get_internal(from)?;
+        let to = self.get_internal(to)?;
+        Ok(self
+            .dijkstra_u32(from, to)?
+            .map(|(path, cost)| (self.resolve_mul_slice(&path), cost)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn weighted_graph() -> DirectedGraphBuilder {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 1.0);
+        builder.add_weighted_edge("B", "C", 1.0);
+        builder.add_weighted_edge("C", "D", 1.0);
+        builder.add_weighted_edge("A", "D", 5.0);
+        builder.add_weighted_edge("A", "E", 0.5);
+        builder.add_edge("E", "D");
+        builder
+    }
+
+    #[test]
+    fn dg_edge_weight() {
+        let dg = weighted_graph().build_directed();
+        assert!(dg.is_weighted());
+        assert_eq!(dg.edge_weight("A", "D").unwrap(), Some(5.0));
+        assert_eq!(dg.edge_weight("E", "D").unwrap(), Some(1.0));
+        assert_eq!(dg.edge_weight("D", "A").unwrap(), None);
+        assert!(dg.edge_weight("A", "Z").is_err());
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_edge("A", "B");
+        let dg = builder.build_directed();
+        assert!(!dg.is_weighted());
+        assert_eq!(dg.edge_weight("A", "B").unwrap(), Some(1.0));
+    }
+
+    #[test]
+    fn dg_edge_weight_last_one_wins() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_weighted_edge("A", "B", 3.0);
+        builder.add_weighted_edge("A", "B", 2.0);
+        let dg = builder.build_directed();
+        assert_eq!(dg.edge_weight("A", "B").unwrap(), Some(2.0));
+    }
+
+    #[test]
+    fn dg_shortest_path_weighted() {
+        let dg = weighted_graph().build_directed();
+        let (path, cost) = dg.shortest_path_weighted("A", "D").unwrap().unwrap();
+        assert_eq!(path, ["A", "E", "D"]);
+        assert_eq!(cost, 1.5);
+
+        let (path, cost) = dg.shortest_path_weighted("A", "A").unwrap().unwrap();
+        assert_eq!(path, ["A"]);
+        assert_eq!(cost, 0.0);
+
+        assert!(dg.shortest_path_weighted("D", "A").unwrap().is_none());
+    }
+
+    #[test]
+    fn dg_shortest_path_weighted_with_cycle() {
+        let mut builder = weighted_graph();
+        builder.add_weighted_edge("D", "A", 1.0);
+        let dg = builder.build_directed();
+        let (path, cost) = dg.shortest_path_weighted("B", "E").unwrap().unwrap();
+        assert_eq!(path, ["B", "C", "D", "A", "E"]);
+        assert_eq!(cost, 3.5);
+    }
+
+    #[test]
+    fn dg_shortest_path_weighted_negative_weight() {
+        let mut builder = weighted_graph();
+        builder.add_weighted_edge("B", "E", -1.0);
+        let dg = builder.build_directed();
+        assert!(matches!(
+            dg.shortest_path_weighted("A", "D"),
+            Err(GraphInteractionError::NegativeWeight(..))
+        ));
+    }
+
+    #[test]
+    fn dg_shortest_path_weighted_nan_weight() {
+        let mut builder = weighted_graph();
+        builder.add_weighted_edge("B", "E", f64::NAN);
+        let dg = builder.build_directed();
+        assert!(matches!(
+            dg.shortest_path_weighted("A", "D"),
+            Err(GraphInteractionError::NaNWeight(..))
+        ));
+    }
+
+    #[test]
+    fn dg_subset_keeps_weights() {
+        let dg = weighted_graph().build_directed();
+        let dg2 = dg.subset("E").unwrap();
+        assert_eq!(dg2.edge_weight("E", "D").unwrap(), Some(1.0));
+        let dg3 = dg.subset("B").unwrap();
+        let (path, cost) = dg3.shortest_path_weighted("B", "D").unwrap().unwrap();
+        assert_eq!(path, ["B", "C", "D"]);
+        assert_eq!(cost, 2.0);
+    }
+}
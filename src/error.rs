@@ -58,6 +58,10 @@ pub enum GraphInteractionError {
     NodeNotExist(Box<str>),
     InternalResolve(u32),
     ZeroSubsetLimit,
+    NegativeWeight(Box<str>, Box<str>),
+    NaNWeight(Box<str>, Box<str>),
+    NaNNodeWeight(Box<str>),
+    LabelNotExist(Box<str>),
 }
 
 impl GraphInteractionError {
@@ -78,6 +82,18 @@ impl std::fmt::Display for GraphInteractionError {
             Self::ZeroSubsetLimit => {
                 write!(f, "Cannot set a `0` limit for a subset operation")
             }
+            Self::NegativeWeight(from, to) => {
+                write!(f, "Edge `{}` -> `{}` has a negative weight", from, to)
+            }
+            Self::NaNWeight(from, to) => {
+                write!(f, "Edge `{}` -> `{}` has a NaN weight", from, to)
+            }
+            Self::NaNNodeWeight(node) => {
+                write!(f, "Node `{}` has a NaN weight", node)
+            }
+            Self::LabelNotExist(label) => {
+                write!(f, "Edge label `{}` does not exist", label)
+            }
         }
     }
 }
@@ -3,14 +3,26 @@ pub mod error;
 pub mod readwrite;
 pub(crate) mod utils;
 
-pub(crate) const CURRENT_VERSION: [u32; 2] = [0, 14];
+pub(crate) const CURRENT_VERSION: [u32; 2] = [0, 19];
+/// Oldest binary version that can still be read. Every field added
+/// since then is optional so older files load with its default.
+pub(crate) const OLDEST_READABLE_VERSION: [u32; 2] = [0, 14];
 
 // Prelude of data types and functionality.
 pub mod prelude {
     pub(crate) type GraphInteractionResult<T> = Result<T, GraphInteractionError>;
-    pub use crate::directed::acyclic::DirectedAcyclicGraph;
+    pub use crate::directed::acyclic::{
+        CriticalPath, DirectedAcyclicGraph, LcaIndex, NodeSchedule,
+    };
     pub use crate::directed::builder::DirectedGraphBuilder;
+    pub use crate::directed::cycles_iter::CyclesIter;
+    pub use crate::directed::diff::GraphDiff;
+    pub use crate::directed::disjoint_paths::DisjointPathsKind;
+    pub use crate::directed::paths_iter::PathsIter;
+    pub use crate::directed::stats::GraphStats;
+    pub use crate::directed::undirected::UndirectedView;
     pub use crate::directed::DirectedGraph;
     pub use crate::error::*;
+    pub use crate::utils::node_attributes::AttributeValue;
     pub use crate::utils::node_set::{NodeVec, NodeVecIter};
 }
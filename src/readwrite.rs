@@ -1,16 +1,16 @@
 #[cfg(feature = "binary")]
 use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
 
-use crate::{error::BinaryError, CURRENT_VERSION};
+use crate::{error::BinaryError, CURRENT_VERSION, OLDEST_READABLE_VERSION};
 
 const fn version_to_bytes() -> [u8; 8] {
-    unsafe { std::mem::transmute(CURRENT_VERSION) }
+    unsafe { std::mem::transmute::<[u32; 2], [u8; 8]>(CURRENT_VERSION) }
 }
 
 fn version_from_reader<R: std::io::Read>(reader: &mut R) -> Result<[u32; 2], std::io::Error> {
     let mut buffer = [0; 8];
     reader.read_exact(&mut buffer)?;
-    Ok(unsafe { std::mem::transmute(buffer) })
+    Ok(unsafe { std::mem::transmute::<[u8; 8], [u32; 2]>(buffer) })
 }
 
 macro_rules! impl_read_write {
@@ -32,7 +32,7 @@ macro_rules! impl_read_write {
                 R: std::io::Read,
             {
                 let version = version_from_reader(&mut reader)?;
-                if version != CURRENT_VERSION {
+                if version < OLDEST_READABLE_VERSION || version > CURRENT_VERSION {
                     return Err(BinaryError::Version(version));
                 }
                 let reader = ZlibDecoder::new(reader);
@@ -47,7 +47,7 @@ impl_read_write!(crate::prelude::DirectedAcyclicGraph);
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::{DirectedGraph, DirectedGraphBuilder};
+    use crate::prelude::{DirectedAcyclicGraph, DirectedGraph, DirectedGraphBuilder};
 
     #[cfg(feature = "binary")]
     #[test]
@@ -63,7 +63,7 @@ mod tests {
 
         let de_dg = DirectedGraph::from_binary(buffer.as_slice()).unwrap();
 
-        assert_eq!(format!("{:?}", dg), format!("{:?}", de_dg));
+        assert_eq!(dg, de_dg);
     }
 
     #[cfg(feature = "binary")]
@@ -80,7 +80,8 @@ mod tests {
 
         let de_dg = DirectedGraph::from_binary(buffer.as_slice()).unwrap();
 
-        assert_eq!(format!("{:?}", dg), format!("{:?}", de_dg));
+        assert_eq!(*dg, de_dg);
+        assert_eq!(dg.content_hash(), de_dg.content_hash());
         assert_eq!(dg.nodes(), de_dg.nodes());
         assert_eq!(dg.get_all_leaves(), de_dg.get_all_leaves());
         assert_eq!(dg.get_all_roots(), de_dg.get_all_roots());
@@ -89,4 +90,146 @@ mod tests {
             de_dg.find_path("1", "50").unwrap()
         );
     }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn directed_weighted_from_and_to_binary() {
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..100 {
+            builder.add_weighted_edge(i.to_string(), (i + 1).to_string(), i as f64 / 2.0);
+        }
+        let dg = builder.clone().build_directed();
+
+        let mut buffer = Vec::new();
+        dg.to_binary(&mut buffer).unwrap();
+
+        let de_dg = DirectedGraph::from_binary(buffer.as_slice()).unwrap();
+
+        assert!(de_dg.is_weighted());
+        assert_eq!(de_dg.edge_weight("10", "11").unwrap(), Some(5.0));
+
+        let dag = builder.build_acyclic().unwrap();
+        let mut buffer = Vec::new();
+        dag.to_binary(&mut buffer).unwrap();
+        let de_dag = DirectedAcyclicGraph::from_binary(buffer.as_slice()).unwrap();
+        assert_eq!(de_dag.edge_weight("10", "11").unwrap(), Some(5.0));
+        assert_eq!(
+            dg.shortest_path_weighted("1", "50").unwrap().unwrap().1,
+            de_dg.shortest_path_weighted("1", "50").unwrap().unwrap().1
+        );
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn directed_with_attributes_from_and_to_binary() {
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..100 {
+            builder.add_edge(i.to_string(), (i + 1).to_string());
+            builder.set_node_attribute(i.to_string(), "value", i);
+        }
+        let dg = builder.build_directed();
+
+        let mut buffer = Vec::new();
+        dg.subset("50").unwrap().to_binary(&mut buffer).unwrap();
+
+        let de_dg = DirectedGraph::from_binary(buffer.as_slice()).unwrap();
+
+        assert_eq!(
+            de_dg
+                .node_attribute("60", "value")
+                .unwrap()
+                .unwrap()
+                .as_int(),
+            Some(60)
+        );
+        assert_eq!(de_dg.node_attribute("10", "value").unwrap(), None);
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn directed_with_labels_from_and_to_binary() {
+        let mut builder = DirectedGraphBuilder::new();
+        for i in 0..100 {
+            let label = if i % 2 == 0 { "even" } else { "odd" };
+            builder.add_labeled_edge(i.to_string(), (i + 1).to_string(), label);
+        }
+        let dg = builder.build_directed();
+
+        let mut buffer = Vec::new();
+        dg.to_binary(&mut buffer).unwrap();
+
+        let de_dg = DirectedGraph::from_binary(buffer.as_slice()).unwrap();
+
+        assert_eq!(de_dg.edge_labels("10", "11").unwrap(), ["even"]);
+        assert_eq!(de_dg.children_by_label(["11"], "odd").unwrap(), ["12"]);
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn directed_multigraph_from_and_to_binary() {
+        let mut builder = DirectedGraphBuilder::new();
+        builder.set_multigraph(true);
+        builder.add_edge("A", "B");
+        builder.add_weighted_edge("A", "B", 3.0);
+        builder.add_edge("B", "C");
+        let dg = builder.build_directed();
+
+        let mut buffer = Vec::new();
+        dg.to_binary(&mut buffer).unwrap();
+
+        let de_dg = DirectedGraph::from_binary(buffer.as_slice()).unwrap();
+
+        assert!(de_dg.is_multigraph());
+        assert_eq!(de_dg.edge_ids("A", "B").unwrap(), [0, 1]);
+        assert_eq!(de_dg.parallel_edge_weights("A", "B").unwrap(), [1.0, 3.0]);
+    }
+
+    /// Writes `value` as a binary tagged with `version`
+    #[cfg(feature = "binary")]
+    fn binary_with_version(version: [u32; 2], value: &serde_cbor::Value) -> Vec<u8> {
+        use std::io::Write;
+        let mut buffer = Vec::new();
+        buffer
+            .write_all(&unsafe { std::mem::transmute::<[u32; 2], [u8; 8]>(version) })
+            .unwrap();
+        let writer = flate2::write::ZlibEncoder::new(&mut buffer, flate2::Compression::default());
+        serde_cbor::to_writer(writer, value).unwrap();
+        buffer
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn directed_reads_older_binary_versions() {
+        use serde_cbor::Value;
+
+        let mut builder = DirectedGraphBuilder::new();
+        builder.add_path(["A", "B", "C"]).unwrap();
+        let dg = builder.build_directed();
+
+        // A 0.14 binary has none of the fields added since then
+        let Value::Map(mut fields) = serde_cbor::value::to_value(&dg).unwrap() else {
+            panic!("a graph is serialized as a map");
+        };
+        for field in [
+            "weights",
+            "attributes",
+            "labels",
+            "edge_ids",
+            "parallel_weights",
+        ] {
+            assert!(fields.remove(&Value::Text(field.into())).is_some());
+        }
+        let old = Value::Map(fields);
+
+        let de_dg = DirectedGraph::from_binary(binary_with_version([0, 14], &old).as_slice());
+        assert_eq!(de_dg.unwrap(), dg);
+
+        for version in [[0, 13], [0, 20], [1, 0]] {
+            let buffer = binary_with_version(version, &old);
+            assert!(matches!(
+                DirectedGraph::from_binary(buffer.as_slice()),
+                Err(crate::error::BinaryError::Version(v)) if v == version
+            ));
+        }
+    }
 }
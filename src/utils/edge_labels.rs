@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use super::{edge_map::EdgeMap, interner::Resolver, node_map::NodeMap, sym::Sym};
+
+/// Labels of every edge. Labels are interned separately from
+/// the nodes.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
+pub(crate) struct EdgeLabels {
+    pub(crate) interner: Arc<Resolver>,
+    /// Sorted labels of each edge
+    pub(crate) labels: EdgeMap<Vec<Sym>>,
+}
+
+impl EdgeLabels {
+    #[inline]
+    pub(crate) fn get(&self, parent: Sym, child: Sym) -> &[Sym] {
+        self.labels
+            .get(parent, child)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn has_any_label(&self, parent: Sym, child: Sym, labels: &[Sym]) -> bool {
+        self.get(parent, child)
+            .iter()
+            .any(|label| labels.contains(label))
+    }
+
+    pub(crate) fn retain_edges(&self, children_map: &NodeMap) -> EdgeLabels {
+        EdgeLabels {
+            interner: Arc::clone(&self.interner),
+            labels: self.labels.retain_edges(children_map),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn reversed(self) -> EdgeLabels {
+        EdgeLabels {
+            interner: self.interner,
+            labels: self.labels.reversed(),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use fxhash::FxHashMap;
+
+use super::{
+    node_map::{LazySet, NodeMap},
+    sym::Sym,
+};
+
+/// Maps an edge `(parent, child)` to a value.
+#[derive(Clone, Debug)]
+pub(crate) struct EdgeMap<T> {
+    map: FxHashMap<(Sym, Sym), T>,
+    /// If `true` the keys of `map` are stored as `(child, parent)`
+    reversed: bool,
+}
+
+impl<T> Default for EdgeMap<T> {
+    fn default() -> Self {
+        Self {
+            map: FxHashMap::default(),
+            reversed: false,
+        }
+    }
+}
+
+impl<T> EdgeMap<T> {
+    #[inline(always)]
+    fn key(&self, parent: Sym, child: Sym) -> (Sym, Sym) {
+        if self.reversed {
+            (child, parent)
+        } else {
+            (parent, child)
+        }
+    }
+    #[inline]
+    pub(crate) fn get(&self, parent: Sym, child: Sym) -> Option<&T> {
+        self.map.get(&self.key(parent, child))
+    }
+    #[inline]
+    pub(crate) fn get_mut(&mut self, parent: Sym, child: Sym) -> Option<&mut T> {
+        let key = self.key(parent, child);
+        self.map.get_mut(&key)
+    }
+    #[inline]
+    pub(crate) fn insert(&mut self, parent: Sym, child: Sym, value: T) -> Option<T> {
+        let key = self.key(parent, child);
+        self.map.insert(key, value)
+    }
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.map.values_mut()
+    }
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Sym, Sym, &T)> {
+        let reversed = self.reversed;
+        self.map.iter().map(move |(&(a, b), value)| match reversed {
+            true => (b, a, value),
+            false => (a, b, value),
+        })
+    }
+    /// Swaps the direction of every edge without touching the
+    /// stored values.
+    #[inline]
+    pub(crate) fn reversed(mut self) -> Self {
+        self.reversed = !self.reversed;
+        self
+    }
+}
+
+impl<T: Clone> EdgeMap<T> {
+    /// Returns a new map containing only the values of the
+    /// edges present in `children_map`.
+    pub(crate) fn retain_edges(&self, children_map: &NodeMap) -> EdgeMap<T> {
+        let mut map = FxHashMap::default();
+        for (parent, children) in children_map.iter() {
+            if let LazySet::Initialized(children) = children {
+                for &child in children.iter() {
+                    if let Some(value) = self.get(parent, child) {
+                        map.insert(self.key(parent, child), value.clone());
+                    }
+                }
+            }
+        }
+        EdgeMap {
+            map,
+            reversed: self.reversed,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for EdgeMap<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.map.len()))?;
+        for (parent, child, value) in self.iter() {
+            seq.serialize_element(&(parent, child, value))?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for EdgeMap<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let values: Vec<(Sym, Sym, T)> = Vec::deserialize(deserializer)?;
+        Ok(EdgeMap {
+            map: values
+                .into_iter()
+                .map(|(parent, child, value)| ((parent, child), value))
+                .collect(),
+            reversed: false,
+        })
+    }
+}
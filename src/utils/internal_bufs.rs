@@ -3,6 +3,7 @@ use std::{cell::UnsafeCell, collections::VecDeque};
 use fxhash::FxHashSet;
 
 use super::sym::Sym;
+use crate::directed::bfs::BidirectionalBfs;
 
 #[derive(Default)]
 pub(crate) struct InternalBufs {
@@ -13,5 +14,6 @@ pub(crate) struct InternalBufs {
     pub(crate) u32x2_vec_0: UnsafeCell<Vec<(Sym, Sym)>>,
     pub(crate) u32x1_queue_0: UnsafeCell<VecDeque<Sym>>,
     pub(crate) u32x1_set_0: UnsafeCell<FxHashSet<Sym>>,
+    pub(crate) bidirectional_bfs_0: UnsafeCell<BidirectionalBfs>,
     pub(crate) usizex2_queue_0: UnsafeCell<VecDeque<(usize, usize)>>,
 }
@@ -1,5 +1,8 @@
+pub mod edge_labels;
+pub mod edge_map;
 pub mod internal_bufs;
 pub mod interner;
+pub mod node_attributes;
 pub mod node_map;
 pub mod node_set;
 pub mod sym;
@@ -0,0 +1,131 @@
+use super::sym::Sym;
+
+/// Value of a node attribute.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(Box<str>),
+}
+
+impl AttributeValue {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(v) => Some(*v),
+            _ => None,
+        }
+    }
+    /// Returns the value as a float. Integers are converted.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(v) => Some(*v),
+            Self::Int(v) => Some(*v as f64),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(v: bool) -> Self {
+        Self::Bool(v)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(v: i64) -> Self {
+        Self::Int(v)
+    }
+}
+
+impl From<i32> for AttributeValue {
+    fn from(v: i32) -> Self {
+        Self::Int(v.into())
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(v: f64) -> Self {
+        Self::Float(v)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(v: &str) -> Self {
+        Self::String(v.into())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(v: String) -> Self {
+        Self::String(v.into())
+    }
+}
+
+/// Table of node attributes. Every attribute is stored as a
+/// column indexed by the symbol of the node.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct NodeAttributes {
+    columns: Vec<(Box<str>, Vec<Option<AttributeValue>>)>,
+}
+
+impl NodeAttributes {
+    pub(crate) fn insert(&mut self, n_nodes: usize, node: Sym, key: &str, value: AttributeValue) {
+        let column = match self.columns.iter().position(|(k, _)| &**k == key) {
+            Some(i) => &mut self.columns[i].1,
+            None => {
+                self.columns.push((key.into(), vec![None; n_nodes]));
+                &mut self.columns.last_mut().expect("We just pushed a column").1
+            }
+        };
+        column[node.into_usize()] = Some(value);
+    }
+
+    pub(crate) fn get(&self, node: Sym, key: &str) -> Option<&AttributeValue> {
+        self.columns
+            .iter()
+            .find(|(k, _)| &**k == key)
+            .and_then(|(_, column)| column.get(node.into_usize()))
+            .and_then(Option::as_ref)
+    }
+
+    pub(crate) fn get_all(&self, node: Sym) -> impl Iterator<Item = (&str, &AttributeValue)> {
+        self.columns.iter().filter_map(move |(key, column)| {
+            column
+                .get(node.into_usize())
+                .and_then(Option::as_ref)
+                .map(|value| (&**key, value))
+        })
+    }
+
+    /// Returns a new table only containing the attributes of
+    /// `nodes`.
+    pub(crate) fn retain_nodes(&self, nodes: &[Sym]) -> NodeAttributes {
+        let columns = self
+            .columns
+            .iter()
+            .map(|(key, column)| {
+                let mut new_column = vec![None; column.len()];
+                for &node in nodes {
+                    new_column[node.into_usize()] = column[node.into_usize()].clone();
+                }
+                (key.clone(), new_column)
+            })
+            .collect();
+        NodeAttributes { columns }
+    }
+}
@@ -24,6 +24,7 @@ impl LazySet {
         }
     }
 
+    #[allow(clippy::wrong_self_convention)]
     pub(crate) fn into_empty(&mut self) {
         *self = LazySet::Empty
     }
@@ -88,11 +89,23 @@ impl NodeMap {
     pub(crate) fn len(&self) -> usize {
         self.map.len()
     }
-    pub(crate) fn initialized_keys(&self) -> Vec<Sym> {
-        (0..self.len())
-            .filter(|i| self.map[*i].is_initialized())
-            .map(|i| Sym::new(i as u32))
-            .collect()
+    /// Iterates over the values of `node`.
+    #[inline]
+    pub(crate) fn values(&self, node: Sym) -> impl Iterator<Item = Sym> + '_ {
+        match self.get(node) {
+            LazySet::Initialized(values) => Some(values.iter().copied()),
+            _ => None,
+        }
+        .into_iter()
+        .flatten()
+    }
+    /// Returns the number of values of `node`.
+    #[inline]
+    pub(crate) fn n_values(&self, node: Sym) -> usize {
+        match self.get(node) {
+            LazySet::Initialized(values) => values.len(),
+            _ => 0,
+        }
     }
     pub(crate) fn initialized_keys_iter(&self) -> impl Iterator<Item = Sym> + '_ {
         (0..self.len())
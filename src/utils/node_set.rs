@@ -1,6 +1,5 @@
 use std::sync::Arc;
 
-
 #[derive(Clone)]
 pub struct NodeVec {
     pub(crate) values: Vec<&'static str>,
@@ -129,8 +128,8 @@ mod tests {
     #[test]
     fn test_serialize_nodevec() {
         let mut builder = DirectedGraphBuilder::new();
-        builder.add_path(["0", "1", "2", "3", "4"]);
-        builder.add_path(["0", "4"]);
+        builder.add_path(["0", "1", "2", "3", "4"]).unwrap();
+        builder.add_path(["0", "4"]).unwrap();
         let graph = builder.build_acyclic().unwrap();
 
         let paths = graph.find_all_paths("0", "4").unwrap();